@@ -0,0 +1,71 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use serde::de::IgnoredAny;
+
+use jma_xml_parser::feed::{EntryReader, Feed};
+use jma_xml_parser::parse_document;
+
+const DOCUMENTS: &[(&str, &str)] = &[
+    ("fuken_r1", include_str!("data/r1.xml")),
+    ("shukan", include_str!("data/shukan.xml")),
+    ("keiho_h27", include_str!("data/keiho.xml")),
+    ("shingen_shindo", include_str!("data/vxse53.xml")),
+    ("tsunami", include_str!("data/vtse41.xml")),
+];
+
+fn documents(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_document");
+    for (name, xml) in DOCUMENTS {
+        group.throughput(Throughput::Bytes(xml.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), xml, |b, xml| {
+            b.iter(|| parse_document(black_box(xml)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+/// 以前のserde-xml-rsによるパースとの比較用
+///
+/// 値を捨てて読むだけなので,serde-xml-rsで各電文の型に変換する場合の上限になる
+fn serde_xml_rs_baseline(c: &mut Criterion) {
+    let mut group = c.benchmark_group("serde_xml_rs_baseline");
+    for (name, xml) in DOCUMENTS {
+        group.throughput(Throughput::Bytes(xml.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), xml, |b, xml| {
+            b.iter(|| serde_xml_rs::from_str::<IgnoredAny>(black_box(xml)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn long_feed(entries: usize) -> String {
+    let mut feed = String::from(
+        r#"<?xml version="1.0" encoding="utf-8"?><feed xmlns="http://www.w3.org/2005/Atom" lang="ja"><title>高頻度（定時）</title><subtitle>JMAXML publishing feed</subtitle><updated>2021-10-01T00:00:00+09:00</updated><id>https://www.data.jma.go.jp/developer/xml/feed/regular_l.xml</id><link rel="self" href="https://www.data.jma.go.jp/developer/xml/feed/regular_l.xml"/><rights type="html">気象庁</rights>"#,
+    );
+    for i in 0..entries {
+        feed.push_str(&format!(
+            r#"<entry><title>府県天気予報（Ｒ１）</title><id>urn:uuid:{:08}</id><updated>2021-10-01T00:00:00Z</updated><author><name>気象庁予報部</name></author><link type="application/xml" href="https://www.data.jma.go.jp/developer/xml/data/{:08}.xml"/><content type="text">【府県天気予報】</content></entry>"#,
+            i, i
+        ));
+    }
+    feed.push_str("</feed>");
+    feed
+}
+
+fn feed(c: &mut Criterion) {
+    let xml = long_feed(1000);
+    let mut group = c.benchmark_group("feed");
+    group.throughput(Throughput::Bytes(xml.len() as u64));
+    group.bench_function("from_str", |b| {
+        b.iter(|| black_box(&xml).parse::<Feed>().unwrap())
+    });
+    group.bench_function("from_reader", |b| {
+        b.iter(|| Feed::from_reader(black_box(xml.as_bytes())).unwrap())
+    });
+    group.bench_function("entry_reader", |b| {
+        b.iter(|| EntryReader::new(black_box(xml.as_bytes())).count())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, documents, serde_xml_rs_baseline, feed);
+criterion_main!(benches);
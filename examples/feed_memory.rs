@@ -0,0 +1,110 @@
+//! `Feed::from_str`と`EntryReader`の最大使用メモリの比較
+//!
+//! `cargo run --release --example feed_memory [entries]`
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use jma_xml_parser::feed::{EntryReader, Feed};
+
+struct CountingAllocator;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let current = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn reset_peak() -> usize {
+    let current = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(current, Ordering::Relaxed);
+    current
+}
+
+fn write_feed(path: &std::path::Path, entries: usize) -> std::io::Result<()> {
+    let mut w = BufWriter::new(File::create(path)?);
+    writeln!(w, r#"<?xml version="1.0" encoding="utf-8"?>"#)?;
+    writeln!(w, r#"<feed xmlns="http://www.w3.org/2005/Atom" lang="ja">"#)?;
+    writeln!(
+        w,
+        "<title>高頻度（定時）</title><subtitle>JMAXML publishing feed</subtitle>"
+    )?;
+    writeln!(w, "<updated>2021-10-01T00:00:00+09:00</updated>")?;
+    writeln!(
+        w,
+        "<id>https://www.data.jma.go.jp/developer/xml/feed/regular_l.xml</id>"
+    )?;
+    writeln!(
+        w,
+        r#"<link rel="self" href="https://www.data.jma.go.jp/developer/xml/feed/regular_l.xml"/>"#
+    )?;
+    writeln!(w, r#"<rights type="html">気象庁</rights>"#)?;
+    for i in 0..entries {
+        writeln!(
+            w,
+            r#"<entry><title>府県天気予報（Ｒ１）</title><id>urn:uuid:{i:08}</id><updated>2021-10-01T00:00:00Z</updated><author><name>気象庁予報部</name></author><link type="application/xml" href="https://www.data.jma.go.jp/developer/xml/data/{i:08}.xml"/><content type="text">【府県天気予報】</content></entry>"#
+        )?;
+    }
+    writeln!(w, "</feed>")?;
+    w.flush()
+}
+
+fn main() {
+    let entries = std::env::args()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(20000);
+    let path = std::env::temp_dir().join("jma_xml_parser_feed_memory.xml");
+    write_feed(&path, entries).unwrap();
+
+    let base = reset_peak();
+    let s = std::fs::read_to_string(&path).unwrap();
+    let feed: Feed = s.parse().unwrap();
+    let count = feed.entries.len();
+    drop((s, feed));
+    println!(
+        "Feed::from_str:     {} entries, peak {} KiB",
+        count,
+        (PEAK.load(Ordering::Relaxed) - base) / 1024
+    );
+
+    let base = reset_peak();
+    let feed = Feed::from_reader(BufReader::new(File::open(&path).unwrap())).unwrap();
+    let count = feed.entries.len();
+    drop(feed);
+    println!(
+        "Feed::from_reader:  {} entries, peak {} KiB",
+        count,
+        (PEAK.load(Ordering::Relaxed) - base) / 1024
+    );
+
+    let base = reset_peak();
+    let count = EntryReader::new(BufReader::new(File::open(&path).unwrap()))
+        .map(Result::unwrap)
+        .count();
+    println!(
+        "EntryReader:        {} entries, peak {} KiB",
+        count,
+        (PEAK.load(Ordering::Relaxed) - base) / 1024
+    );
+
+    std::fs::remove_file(&path).unwrap();
+}
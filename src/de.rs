@@ -0,0 +1,782 @@
+//! XMLの読み込み
+//!
+//! 各レポート,フィードのパースはこのモジュールを通す。
+//! quick-xmlで名前空間を解決しながら要素の木を作り,serdeで各型に変換する。
+//!
+//! 要素は名前空間URIで区別する。要素基盤(`jmx_eb`)と付加事項(`jmx_add`)の要素は
+//! 文書中の接頭辞によらず`jmx_eb:Weather`,`jmx_add:Addition`のような名前になり,
+//! それ以外の名前空間の要素と属性はローカル名で対応付ける。
+//! 要素の文字列は前後の空白を除いて`$value`とする。
+
+use std::fmt;
+use std::io::BufRead;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::{Namespace, ResolveResult};
+use quick_xml::NsReader;
+use serde::de::value::StrDeserializer;
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+
+use crate::error::{ParseError, XmlError};
+
+const JMX_EB: &[u8] = b"http://xml.kishou.go.jp/jmaxml1/elementBasis1/";
+const JMX_ADD: &[u8] = b"http://xml.kishou.go.jp/jmaxml1/addition1/";
+
+pub(crate) fn from_str<T: DeserializeOwned>(s: &str) -> Result<T, ParseError> {
+    from_reader(s.as_bytes())
+}
+
+pub(crate) fn from_reader<T: DeserializeOwned, R: BufRead>(reader: R) -> Result<T, ParseError> {
+    Document::from_reader(reader)?.deserialize()
+}
+
+/// 読み込んだ電文全体の要素の木
+///
+/// 1度だけ読み込んで複数の型に変換するときに使う
+pub(crate) struct Document {
+    root: Element,
+}
+
+impl Document {
+    pub(crate) fn from_str(s: &str) -> Result<Self, ParseError> {
+        Document::from_reader(s.as_bytes())
+    }
+
+    pub(crate) fn from_reader<R: BufRead>(reader: R) -> Result<Self, ParseError> {
+        let mut reader = NsReader::from_reader(reader);
+        let root = read_element(&mut reader, &mut Vec::new(), Vec::new())?
+            .ok_or(Error::new(ErrorKind::UnexpectedEof))?;
+        Ok(Document { root })
+    }
+
+    pub(crate) fn deserialize<T: DeserializeOwned>(&self) -> Result<T, ParseError> {
+        T::deserialize(ElementDeserializer(&self.root)).map_err(|mut e| {
+            // 位置はルート要素からの相対で表す
+            e.path.pop();
+            e.into()
+        })
+    }
+}
+
+/// ルート要素直下の要素を1つずつ読み出す
+pub(crate) struct ChildReader<R> {
+    reader: NsReader<R>,
+    buf: Vec<u8>,
+    depth: usize,
+    done: bool,
+}
+
+impl<R: BufRead> ChildReader<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        ChildReader {
+            reader: NsReader::from_reader(reader),
+            buf: Vec::new(),
+            depth: 0,
+            done: false,
+        }
+    }
+
+    /// 次の`name`の要素まで読み進めて変換する
+    pub(crate) fn next<T: DeserializeOwned>(
+        &mut self,
+        name: &str,
+    ) -> Option<Result<T, ParseError>> {
+        if self.done {
+            return None;
+        }
+        let result = self.next_element(name).transpose()?.and_then(|element| {
+            T::deserialize(ElementDeserializer(&element)).map_err(ParseError::from)
+        });
+        self.done = result.is_err();
+        Some(result)
+    }
+
+    fn next_element(&mut self, name: &str) -> Result<Option<Element>, ParseError> {
+        loop {
+            self.buf.clear();
+            let (ns, event) = self
+                .reader
+                .read_resolved_event_into(&mut self.buf)
+                .map_err(Error::from)?;
+            match event {
+                Event::Start(e) => {
+                    let element = start_element(ns, &e)?;
+                    if self.depth == 1 && element.name == name {
+                        let mut buf = Vec::new();
+                        return Ok(read_element(&mut self.reader, &mut buf, vec![element])?);
+                    }
+                    self.depth += 1;
+                }
+                Event::Empty(e) => {
+                    let element = start_element(ns, &e)?;
+                    if self.depth == 1 && element.name == name {
+                        return Ok(Some(element));
+                    }
+                }
+                Event::End(_) => self.depth -= 1,
+                Event::Eof if self.depth == 0 => {
+                    self.done = true;
+                    return Ok(None);
+                }
+                Event::Eof => return Err(Error::new(ErrorKind::UnexpectedEof).into()),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// 名前空間を解決した要素
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Element>,
+    text: String,
+}
+
+fn start_element(ns: ResolveResult, e: &BytesStart) -> Result<Element, Error> {
+    let local = std::str::from_utf8(e.local_name().into_inner()).map_err(quick_xml::Error::from)?;
+    let name = match ns {
+        ResolveResult::Bound(Namespace(JMX_EB)) => format!("jmx_eb:{}", local),
+        ResolveResult::Bound(Namespace(JMX_ADD)) => format!("jmx_add:{}", local),
+        ResolveResult::Unknown(prefix) => {
+            return Err(Error::new(ErrorKind::UnknownPrefix(
+                String::from_utf8_lossy(&prefix).into_owned(),
+            )))
+        }
+        _ => local.to_owned(),
+    };
+    let mut attributes = Vec::new();
+    for attribute in e.attributes() {
+        let attribute = attribute.map_err(quick_xml::Error::from)?;
+        if attribute.key.as_namespace_binding().is_some() {
+            continue;
+        }
+        let key = std::str::from_utf8(attribute.key.local_name().into_inner())
+            .map_err(quick_xml::Error::from)?
+            .to_owned();
+        attributes.push((key, attribute.unescape_value()?.into_owned()));
+    }
+    Ok(Element {
+        name,
+        attributes,
+        ..Element::default()
+    })
+}
+
+/// `stack`の要素が全て閉じるまで読み,最も外側の要素を返す
+///
+/// `stack`が空の場合は次に現れる要素を読む。要素が無いまま終端に達した場合はNone
+fn read_element<R: BufRead>(
+    reader: &mut NsReader<R>,
+    buf: &mut Vec<u8>,
+    mut stack: Vec<Element>,
+) -> Result<Option<Element>, Error> {
+    loop {
+        buf.clear();
+        let (ns, event) = reader.read_resolved_event_into(buf)?;
+        let closed = match event {
+            Event::Start(e) => {
+                stack.push(start_element(ns, &e)?);
+                None
+            }
+            Event::Empty(e) => Some(start_element(ns, &e)?),
+            Event::End(_) => stack.pop(),
+            Event::Text(e) => {
+                if let Some(element) = stack.last_mut() {
+                    element.text.push_str(&e.unescape()?);
+                }
+                None
+            }
+            Event::CData(e) => {
+                if let Some(element) = stack.last_mut() {
+                    element
+                        .text
+                        .push_str(std::str::from_utf8(&e).map_err(quick_xml::Error::from)?);
+                }
+                None
+            }
+            Event::Eof if stack.is_empty() => return Ok(None),
+            Event::Eof => return Err(Error::new(ErrorKind::UnexpectedEof)),
+            _ => None,
+        };
+        if let Some(mut element) = closed {
+            let trimmed = element.text.trim();
+            if trimmed.len() != element.text.len() {
+                element.text = trimmed.to_owned();
+            }
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(element),
+                None => return Ok(Some(element)),
+            }
+        }
+    }
+}
+
+/// 読み込み,変換時のエラー
+#[derive(Debug)]
+pub(crate) struct Error {
+    kind: ErrorKind,
+    /// エラーが起きた要素,属性の名前,内側から順
+    path: Vec<String>,
+}
+
+#[derive(Debug)]
+enum ErrorKind {
+    Syntax(quick_xml::Error),
+    UnexpectedEof,
+    UnknownPrefix(String),
+    MissingField(&'static str),
+    InvalidValue(String),
+    Custom(String),
+}
+
+impl Error {
+    fn new(kind: ErrorKind) -> Self {
+        Error {
+            kind,
+            path: Vec::new(),
+        }
+    }
+
+    fn at(mut self, name: &str) -> Self {
+        self.path.push(name.to_owned());
+        self
+    }
+
+    fn with_value(mut self, value: &str) -> Self {
+        if let ErrorKind::Custom(_) = self.kind {
+            self.kind = ErrorKind::InvalidValue(value.to_owned());
+        }
+        self
+    }
+
+    /// `Body/Warning/Item`,`Body/Warning@type`のような位置
+    pub(crate) fn path(&self) -> String {
+        let mut path = String::new();
+        for name in self.path.iter().rev() {
+            if !path.is_empty() && !name.starts_with('@') {
+                path.push('/');
+            }
+            path.push_str(name);
+        }
+        if let ErrorKind::MissingField(field) = self.kind {
+            if !path.is_empty() {
+                path.push('/');
+            }
+            path.push_str(field);
+        }
+        path
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ErrorKind::Syntax(e) => write!(f, "{}", e),
+            ErrorKind::UnexpectedEof => write!(f, "unexpected end of document"),
+            ErrorKind::UnknownPrefix(prefix) => write!(f, "unknown namespace prefix {}", prefix),
+            ErrorKind::MissingField(_) => write!(f, "missing {}", self.path()),
+            ErrorKind::InvalidValue(value) => {
+                write!(f, "invalid value at {}: {}", self.path(), value)
+            }
+            ErrorKind::Custom(message) => write!(f, "{} at {}", message, self.path()),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            ErrorKind::Syntax(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::new(ErrorKind::Custom(msg.to_string()))
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        Error::new(ErrorKind::MissingField(field))
+    }
+
+    fn unknown_variant(variant: &str, _expected: &'static [&'static str]) -> Self {
+        Error::new(ErrorKind::InvalidValue(variant.to_owned()))
+    }
+}
+
+impl From<quick_xml::Error> for Error {
+    fn from(e: quick_xml::Error) -> Self {
+        Error::new(ErrorKind::Syntax(e))
+    }
+}
+
+impl From<Error> for ParseError {
+    fn from(e: Error) -> Self {
+        match &e.kind {
+            ErrorKind::Syntax(_) | ErrorKind::UnexpectedEof | ErrorKind::UnknownPrefix(_) => {
+                ParseError::Syntax(XmlError(e))
+            }
+            ErrorKind::MissingField(_) => ParseError::MissingElement {
+                path: e.path().into(),
+            },
+            ErrorKind::InvalidValue(value) => ParseError::InvalidValue {
+                path: e.path().into(),
+                value: value.clone(),
+            },
+            ErrorKind::Custom(_) => ParseError::Deserialize(XmlError(e)),
+        }
+    }
+}
+
+/// 属性の値,要素の文字列
+struct TextDeserializer<'a>(&'a str);
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            match self.0.trim().parse() {
+                Ok(value) => visitor.$visit(value),
+                Err(_) => Err(Error::new(ErrorKind::InvalidValue(self.0.to_owned()))),
+            }
+        }
+    )*};
+}
+
+impl<'de, 'a> de::Deserializer<'de> for TextDeserializer<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor
+            .visit_str(self.0)
+            .map_err(|e: Error| e.with_value(self.0))
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0.trim() {
+            "true" | "1" => visitor.visit_bool(true),
+            "false" | "0" => visitor.visit_bool(false),
+            _ => Err(Error::new(ErrorKind::InvalidValue(self.0.to_owned()))),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let variant: StrDeserializer<Error> = self.0.into_deserializer();
+        visitor.visit_enum(variant)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        char str string bytes byte_buf seq tuple tuple_struct map struct identifier
+    }
+}
+
+/// 1つの要素
+struct ElementDeserializer<'a>(&'a Element);
+
+macro_rules! forward_to_text {
+    ($($method:ident)*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            TextDeserializer(&self.0.text)
+                .$method(visitor)
+                .map_err(|e| e.at(&self.0.name))
+        }
+    )*};
+}
+
+impl<'de, 'a> de::Deserializer<'de> for ElementDeserializer<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.0.attributes.is_empty() && self.0.children.is_empty() {
+            self.deserialize_str(visitor)
+        } else {
+            self.deserialize_map(visitor)
+        }
+    }
+
+    forward_to_text! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_f32 deserialize_f64 deserialize_char deserialize_str deserialize_string
+        deserialize_bytes deserialize_byte_buf deserialize_unit deserialize_identifier
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(SeqAccess(vec![self.0].into_iter()))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor
+            .visit_map(MapAccess::new(self.0))
+            .map_err(|e| e.at(&self.0.name))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        TextDeserializer(&self.0.text)
+            .deserialize_enum(name, variants, visitor)
+            .map_err(|e| e.at(&self.0.name))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+}
+
+/// 同じ名前の子要素の並び
+struct GroupDeserializer<'a> {
+    name: &'a str,
+    elements: Vec<&'a Element>,
+}
+
+impl<'a> GroupDeserializer<'a> {
+    fn single(self) -> Result<ElementDeserializer<'a>, Error> {
+        match self.elements.as_slice() {
+            [element] => Ok(ElementDeserializer(element)),
+            _ => Err(Error::new(ErrorKind::Custom("duplicated element".to_owned())).at(self.name)),
+        }
+    }
+}
+
+macro_rules! forward_to_single {
+    ($($method:ident)*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            self.single()?.$method(visitor)
+        }
+    )*};
+}
+
+impl<'de, 'a> de::Deserializer<'de> for GroupDeserializer<'a> {
+    type Error = Error;
+
+    forward_to_single! {
+        deserialize_any deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32
+        deserialize_i64 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_f32 deserialize_f64 deserialize_char deserialize_str deserialize_string
+        deserialize_bytes deserialize_byte_buf deserialize_unit deserialize_map
+        deserialize_identifier
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.single()?.deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(SeqAccess(self.elements.into_iter()))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.single()?.deserialize_struct(name, fields, visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.single()?.deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+}
+
+struct SeqAccess<'a>(std::vec::IntoIter<&'a Element>);
+
+impl<'de, 'a> de::SeqAccess<'de> for SeqAccess<'a> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        self.0
+            .next()
+            .map(|element| seed.deserialize(ElementDeserializer(element)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+enum Value<'a> {
+    Attribute(&'a str, &'a str),
+    Group(GroupDeserializer<'a>),
+    Text(&'a str),
+}
+
+/// 属性,子要素(名前毎),文字列の順に並べた要素の内容
+struct MapAccess<'a> {
+    entries: std::vec::IntoIter<Value<'a>>,
+    value: Option<Value<'a>>,
+}
+
+impl<'a> MapAccess<'a> {
+    fn new(element: &'a Element) -> Self {
+        let mut entries: Vec<Value> = element
+            .attributes
+            .iter()
+            .map(|(key, value)| Value::Attribute(key, value))
+            .collect();
+        let attributes = entries.len();
+        for child in &element.children {
+            let group = entries[attributes..]
+                .iter_mut()
+                .find_map(|entry| match entry {
+                    Value::Group(group) if group.name == child.name => Some(group),
+                    _ => None,
+                });
+            match group {
+                Some(group) => group.elements.push(child),
+                None => entries.push(Value::Group(GroupDeserializer {
+                    name: &child.name,
+                    elements: vec![child],
+                })),
+            }
+        }
+        if !element.text.is_empty() {
+            entries.push(Value::Text(&element.text));
+        }
+        MapAccess {
+            entries: entries.into_iter(),
+            value: None,
+        }
+    }
+}
+
+impl<'de, 'a> de::MapAccess<'de> for MapAccess<'a> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let value = match self.entries.next() {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        let key = match &value {
+            Value::Attribute(key, _) => key,
+            Value::Group(group) => group.name,
+            Value::Text(_) => "$value",
+        };
+        let key = seed.deserialize(TextDeserializer(key)).map(Some);
+        self.value = Some(value);
+        key
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        match self.value.take() {
+            Some(Value::Attribute(key, value)) => seed
+                .deserialize(TextDeserializer(value))
+                .map_err(|e| e.at(&format!("@{}", key))),
+            Some(Value::Group(group)) => seed.deserialize(group),
+            Some(Value::Text(text)) => seed.deserialize(TextDeserializer(text)),
+            None => Err(Error::new(ErrorKind::Custom(
+                "value requested before key".to_owned(),
+            ))),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_derive::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Part {
+        #[serde(rename = "jmx_eb:Weather")]
+        weather: Option<Weather>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Weather {
+        #[serde(rename = "type")]
+        _type: String,
+        #[serde(rename = "$value")]
+        value: String,
+    }
+
+    #[test]
+    fn resolve_namespace_regardless_of_prefix() {
+        let expected = Part {
+            weather: Some(Weather {
+                _type: "天気".to_owned(),
+                value: "晴れ".to_owned(),
+            }),
+        };
+        for xml in [
+            r#"<Part xmlns:jmx_eb="http://xml.kishou.go.jp/jmaxml1/elementBasis1/"><jmx_eb:Weather type="天気">晴れ</jmx_eb:Weather></Part>"#,
+            r#"<Part xmlns:eb="http://xml.kishou.go.jp/jmaxml1/elementBasis1/"><eb:Weather type="天気"> 晴れ </eb:Weather></Part>"#,
+            r#"<Part><Weather xmlns="http://xml.kishou.go.jp/jmaxml1/elementBasis1/" type="天気">晴れ</Weather></Part>"#,
+        ] {
+            assert_eq!(from_str::<Part>(xml).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn ignore_other_namespace() {
+        let xml = r#"<Part xmlns:jmx_eb="http://example.com/"><jmx_eb:Weather type="天気">晴れ</jmx_eb:Weather></Part>"#;
+        assert_eq!(from_str::<Part>(xml).unwrap(), Part { weather: None });
+    }
+
+    #[test]
+    fn reject_malformed_xml() {
+        for xml in [
+            "",
+            "<Part>",
+            "<Part></Weather>",
+            "<Part><eb:Weather>晴れ</eb:Weather></Part>",
+        ] {
+            assert!(from_str::<Part>(xml).is_err(), "{}", xml);
+        }
+    }
+}
@@ -0,0 +1,301 @@
+//! Control/Title,Head/InfoKindによる電文の振り分け
+
+use serde_derive::Deserialize;
+#[cfg(feature = "serialize")]
+use serde_derive::Serialize;
+
+use crate::de;
+use crate::error::ParseError;
+use crate::fuken_r1::{Addition, Control, Head};
+use crate::{fuken_r1, keiho_h27, shingen_shindo, shukan, tsunami};
+
+/// 種類毎にパースされた電文
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+#[allow(clippy::large_enum_variant)]
+pub enum JmaDocument {
+    /// 府県天気予報（Ｒ１）
+    FukenR1(fuken_r1::Report),
+    /// 府県週間天気予報
+    Shukan(shukan::Report),
+    /// 気象警報・注意報（Ｈ２７）
+    KeihoH27(keiho_h27::Report),
+    /// 震度速報,震源に関する情報,震源・震度に関する情報
+    ShingenShindo(shingen_shindo::Report),
+    /// 津波警報・注意報・予報,津波情報,沖合の津波観測に関する情報
+    Tsunami(tsunami::Report),
+    /// 未対応の電文
+    Unsupported {
+        control: Control,
+        head: Head,
+        addition: Option<Addition>,
+        raw: String,
+    },
+}
+
+impl JmaDocument {
+    pub fn control(&self) -> &Control {
+        match self {
+            JmaDocument::FukenR1(r) => &r.control,
+            JmaDocument::Shukan(r) => &r.control,
+            JmaDocument::KeihoH27(r) => &r.control,
+            JmaDocument::ShingenShindo(r) => &r.control,
+            JmaDocument::Tsunami(r) => &r.control,
+            JmaDocument::Unsupported { control, .. } => control,
+        }
+    }
+
+    pub fn head(&self) -> &Head {
+        match self {
+            JmaDocument::FukenR1(r) => &r.head,
+            JmaDocument::Shukan(r) => &r.head,
+            JmaDocument::KeihoH27(r) => &r.head,
+            JmaDocument::ShingenShindo(r) => &r.head,
+            JmaDocument::Tsunami(r) => &r.head,
+            JmaDocument::Unsupported { head, .. } => head,
+        }
+    }
+
+    /// 付加事項
+    pub fn addition(&self) -> Option<&Addition> {
+        match self {
+            JmaDocument::FukenR1(r) => r.addition.as_ref(),
+            JmaDocument::Shukan(r) => r.addition.as_ref(),
+            JmaDocument::KeihoH27(r) => r.addition.as_ref(),
+            JmaDocument::ShingenShindo(r) => r.addition.as_ref(),
+            JmaDocument::Tsunami(r) => r.addition.as_ref(),
+            JmaDocument::Unsupported { addition, .. } => addition.as_ref(),
+        }
+    }
+}
+
+/// Control/TitleとHead/InfoKindを見て対応するパーサで電文をパースする
+///
+/// 要素の木は1度だけ作り,ヘッダの判定と各電文への変換で共有する
+pub fn parse_document(s: &str) -> Result<JmaDocument, ParseError> {
+    let tree = de::Document::from_str(s)?;
+    let PeekTmp {
+        control,
+        head,
+        addition,
+    } = tree.deserialize()?;
+    let document = match (control.title.as_str(), head.info_kind.as_str()) {
+        ("府県天気予報（Ｒ１）", _) => {
+            JmaDocument::FukenR1(fuken_r1::Report::from_document(&tree)?)
+        }
+        ("府県週間天気予報", _) => {
+            JmaDocument::Shukan(shukan::Report::from_document(&tree)?)
+        }
+        ("気象警報・注意報（Ｈ２７）", _) => {
+            JmaDocument::KeihoH27(keiho_h27::Report::from_document(&tree)?)
+        }
+        ("震度速報", _) | ("震源に関する情報", _) | ("震源・震度に関する情報", _) => {
+            JmaDocument::ShingenShindo(shingen_shindo::Report::from_document(&tree)?)
+        }
+        ("沖合の津波観測に関する情報", _) | (_, "津波警報・注意報・予報") | (_, "津波情報") => {
+            JmaDocument::Tsunami(tsunami::Report::from_document(&tree)?)
+        }
+        _ => JmaDocument::Unsupported {
+            control,
+            head,
+            addition,
+            raw: s.to_owned(),
+        },
+    };
+    Ok(document)
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct PeekTmp {
+    #[serde(alias = "Control")]
+    control: Control,
+    #[serde(alias = "Head")]
+    head: Head,
+    #[serde(alias = "jmx_add:Addition")]
+    addition: Option<Addition>,
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "serialize")]
+    use serde_json::Value;
+
+    use super::*;
+    #[cfg(feature = "serialize")]
+    use crate::feed::Feed;
+
+    const DOCUMENTS: &[&str] = &[
+        include_str!("../benches/data/r1.xml"),
+        include_str!("../benches/data/shukan.xml"),
+        include_str!("../benches/data/keiho.xml"),
+        include_str!("../benches/data/vxse53.xml"),
+        include_str!("../benches/data/vtse41.xml"),
+    ];
+
+    /// 電文の種類毎の振り分け先
+    #[test]
+    fn dispatch_by_title_and_info_kind() {
+        let kind = |xml: &str| match parse_document(xml).unwrap() {
+            JmaDocument::FukenR1(_) => "FukenR1",
+            JmaDocument::Shukan(_) => "Shukan",
+            JmaDocument::KeihoH27(_) => "KeihoH27",
+            JmaDocument::ShingenShindo(_) => "ShingenShindo",
+            JmaDocument::Tsunami(_) => "Tsunami",
+            JmaDocument::Unsupported { .. } => "Unsupported",
+        };
+        let expected = ["FukenR1", "Shukan", "KeihoH27", "ShingenShindo", "Tsunami"];
+        for (xml, expected) in DOCUMENTS.iter().zip(expected) {
+            assert_eq!(kind(xml), expected);
+        }
+        assert_eq!(kind(include_str!("../benches/data/vpww54.xml")), "KeihoH27");
+        assert_eq!(kind(include_str!("../benches/data/vpfw50.xml")), "Shukan");
+
+        // 震源・震度に関する情報以外の地震情報
+        for title in ["震度速報", "震源に関する情報"] {
+            let xml = DOCUMENTS[3].replacen(
+                "<Title>震源・震度に関する情報</Title>",
+                &format!("<Title>{}</Title>", title),
+                1,
+            );
+            assert_eq!(kind(&xml), "ShingenShindo", "{}", title);
+        }
+
+        // 津波の電文はHead/InfoKindで判定する
+        let xml = DOCUMENTS[4].replace(
+            "<InfoKind>津波警報・注意報・予報</InfoKind>",
+            "<InfoKind>津波情報</InfoKind>",
+        );
+        assert_eq!(kind(&xml), "Tsunami");
+    }
+
+    #[test]
+    fn addition() {
+        let document = parse_document(include_str!("../benches/data/vpww54.xml")).unwrap();
+        assert!(matches!(document, JmaDocument::KeihoH27(_)));
+        assert_eq!(document.addition().unwrap().notices.len(), 2);
+
+        // Body中のKind/Additionは含まない
+        let document = parse_document(DOCUMENTS[2]).unwrap();
+        assert!(matches!(document, JmaDocument::KeihoH27(_)));
+        assert!(document.addition().is_none());
+
+        let xml = include_str!("../benches/data/vpww54.xml").replacen(
+            "<Title>気象警報・注意報（Ｈ２７）</Title>",
+            "<Title>気象警報・注意報（未知）</Title>",
+            1,
+        );
+        let document = parse_document(&xml).unwrap();
+        assert!(matches!(document, JmaDocument::Unsupported { .. }));
+        assert_eq!(
+            document.addition().unwrap().notices[1],
+            "詳しくは気象庁ホームページをご覧ください。"
+        );
+    }
+
+    #[test]
+    fn unsupported_document() {
+        let xml = DOCUMENTS[0].replacen(
+            "<Title>府県天気予報（Ｒ１）</Title>",
+            "<Title>府県天気予報（未知）</Title>",
+            1,
+        );
+        match parse_document(&xml).unwrap() {
+            JmaDocument::Unsupported {
+                control,
+                head,
+                addition,
+                raw,
+            } => {
+                assert_eq!(control.title, "府県天気予報（未知）");
+                assert_eq!(head.info_kind, "府県天気予報");
+                assert!(addition.is_none());
+                assert_eq!(raw, xml);
+            }
+            d => panic!("{:?}", d),
+        }
+    }
+
+    #[test]
+    fn supported_document_with_broken_body_is_an_error() {
+        let xml = DOCUMENTS[0].replace("<Code>130010</Code>", "<Code>東京</Code>");
+        assert!(parse_document(&xml).is_err());
+        assert!(parse_document("<Report>").is_err());
+    }
+
+    /// JSON→Rust→JSONで値が変わらないこと
+    #[cfg(feature = "serialize")]
+    fn assert_lossless<T: serde::Serialize + serde::de::DeserializeOwned>(json: Value) {
+        let value: T = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&value).unwrap(), json);
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn json_round_trip_document() {
+        let unsupported = DOCUMENTS[0].replacen(
+            "<Title>府県天気予報（Ｒ１）</Title>",
+            "<Title>府県天気予報（未知）</Title>",
+            1,
+        );
+        for xml in DOCUMENTS.iter().copied().chain([unsupported.as_str()]) {
+            let document = parse_document(xml).unwrap();
+            let json = serde_json::to_value(&document).unwrap();
+            assert_lossless::<JmaDocument>(json.clone());
+            let report = match (&document, json) {
+                (JmaDocument::FukenR1(_), Value::Object(mut o)) => o.remove("FukenR1").unwrap(),
+                _ => continue,
+            };
+            assert_lossless::<fuken_r1::Report>(report);
+        }
+    }
+
+    /// JSONのキーを全て集める
+    #[cfg(feature = "serialize")]
+    fn collect_keys(value: &Value, keys: &mut Vec<String>) {
+        match value {
+            Value::Object(o) => {
+                for (k, v) in o {
+                    keys.push(k.clone());
+                    collect_keys(v, keys);
+                }
+            }
+            Value::Array(a) => a.iter().for_each(|v| collect_keys(v, keys)),
+            _ => {}
+        }
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn datetime_field_names() {
+        let mut all = Vec::new();
+        for xml in DOCUMENTS
+            .iter()
+            .copied()
+            .chain([include_str!("../benches/data/vpww54.xml")])
+        {
+            let document = parse_document(xml).unwrap();
+            collect_keys(&serde_json::to_value(&document).unwrap(), &mut all);
+        }
+        assert!(all.iter().all(|k| !k.contains("date_time")), "{:?}", all);
+        for key in [
+            "datetime",
+            "report_datetime",
+            "target_datetime",
+            "high_tide_datetime",
+        ] {
+            assert!(all.iter().any(|k| k == key), "{}", key);
+        }
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn json_round_trip_feed() {
+        let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom" lang="ja"><title>高頻度（定時）</title><subtitle>JMAXML publishing feed</subtitle><updated>2021-10-01T02:00:00+09:00</updated><id>urn:uuid:feed</id><link rel="self" href="https://www.data.jma.go.jp/developer/xml/feed/regular.xml"/><rights type="html">rights</rights><entry><title>府県天気予報（Ｒ１）</title><id>urn:uuid:entry</id><updated>2021-10-01T01:59:19Z</updated><author><name>気象庁</name></author><link type="application/xml" href="https://www.data.jma.go.jp/developer/xml/data/r1.xml"/><content type="text">【府県天気予報】</content></entry></feed>"#;
+        let feed: Feed = xml.parse().unwrap();
+        let json = serde_json::to_value(&feed).unwrap();
+        assert_eq!(json["entries"][0]["content"]["value"], "【府県天気予報】");
+        assert_eq!(json["rights"]["item"], "rights");
+        assert_lossless::<Feed>(json);
+    }
+}
@@ -0,0 +1,218 @@
+//! ISO 8601の期間
+
+use chrono::{DateTime, Duration, FixedOffset};
+
+use crate::error::DurationError;
+use crate::fuken_r1::{Head, MeteorologicalInfo, TimeDefine};
+use crate::shukan::Normal;
+
+/// 「PT6H」「P1D」等を固定長の期間にする
+///
+/// 年,月を含むものは[`DurationError::Calendar`]になる
+pub fn parse_duration(s: &str) -> Result<Duration, DurationError> {
+    let invalid = || DurationError::Invalid(s.to_owned());
+    let rest = s.trim().strip_prefix('P').ok_or_else(invalid)?;
+    let (date, time) = match rest.split_once('T') {
+        Some((_, "")) => return Err(invalid()),
+        Some((date, time)) => (date, Some(time)),
+        None => (rest, None),
+    };
+    if date.is_empty() && time.is_none() {
+        return Err(invalid());
+    }
+    let mut calendar = false;
+    let mut total = Duration::zero();
+    for (part, units) in [(date, "YMWD"), (time.unwrap_or_default(), "HMS")] {
+        let mut rest = part;
+        let mut last_unit = None;
+        while !rest.is_empty() {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .ok_or_else(invalid)?;
+            let (number, tail) = rest.split_at(end);
+            let unit = tail.chars().next().ok_or_else(invalid)?;
+            let position = units.find(unit).ok_or_else(invalid)?;
+            if number.is_empty() || matches!(last_unit, Some(last) if last >= position) {
+                return Err(invalid());
+            }
+            last_unit = Some(position);
+            let value: f64 = number.parse().map_err(|_| invalid())?;
+            let seconds = match (units, unit) {
+                ("YMWD", 'Y') | ("YMWD", 'M') => {
+                    calendar = true;
+                    0.0
+                }
+                ("YMWD", 'W') => value * 7.0 * 86400.0,
+                ("YMWD", 'D') => value * 86400.0,
+                (_, 'H') => value * 3600.0,
+                (_, 'M') => value * 60.0,
+                _ => value,
+            };
+            // chrono::Durationの範囲(約2.9億年)を十分に下回る値のみ扱う
+            if !(0.0..1e15).contains(&seconds) {
+                return Err(invalid());
+            }
+            total = total
+                .checked_add(&Duration::milliseconds((seconds * 1000.0).round() as i64))
+                .ok_or_else(invalid)?;
+            rest = &tail[unit.len_utf8()..];
+        }
+    }
+    if calendar {
+        return Err(DurationError::Calendar(s.to_owned()));
+    }
+    Ok(total)
+}
+
+/// 2つの区間が重なるか
+///
+/// 区間は終わりを含まず,長さ0の区間は時刻1点として扱う
+pub fn overlaps(
+    (start_a, end_a): (DateTime<FixedOffset>, DateTime<FixedOffset>),
+    (start_b, end_b): (DateTime<FixedOffset>, DateTime<FixedOffset>),
+) -> bool {
+    let contains =
+        |start: DateTime<FixedOffset>, end: DateTime<FixedOffset>, t: DateTime<FixedOffset>| {
+            if start == end {
+                start == t
+            } else {
+                start <= t && t < end
+            }
+        };
+    if start_a == end_a {
+        contains(start_b, end_b, start_a)
+    } else if start_b == end_b {
+        contains(start_a, end_a, start_b)
+    } else {
+        start_a < end_b && start_b < end_a
+    }
+}
+
+impl TimeDefine {
+    pub fn parse_duration(&self) -> Result<Option<Duration>, DurationError> {
+        self.duration.as_deref().map(parse_duration).transpose()
+    }
+
+    /// DateTimeから期間の終わりまで,期間がなければDateTimeのみの区間
+    pub fn interval(
+        &self,
+    ) -> Result<(DateTime<FixedOffset>, DateTime<FixedOffset>), DurationError> {
+        let duration = self.parse_duration()?.unwrap_or_else(Duration::zero);
+        Ok((self.datetime, self.datetime + duration))
+    }
+
+    pub fn overlaps(&self, other: &TimeDefine) -> Result<bool, DurationError> {
+        Ok(overlaps(self.interval()?, other.interval()?))
+    }
+}
+
+impl MeteorologicalInfo {
+    pub fn parse_duration(&self) -> Result<Duration, DurationError> {
+        parse_duration(&self.duration)
+    }
+
+    pub fn interval(
+        &self,
+    ) -> Result<(DateTime<FixedOffset>, DateTime<FixedOffset>), DurationError> {
+        Ok((self.date_time, self.date_time + self.parse_duration()?))
+    }
+}
+
+impl Head {
+    pub fn parse_target_duration(&self) -> Result<Option<Duration>, DurationError> {
+        self.target_duration
+            .as_deref()
+            .map(parse_duration)
+            .transpose()
+    }
+
+    /// TargetDateTimeからTargetDurationの終わりまで
+    pub fn target_interval(
+        &self,
+    ) -> Result<(DateTime<FixedOffset>, DateTime<FixedOffset>), DurationError> {
+        let duration = self.parse_target_duration()?.unwrap_or_else(Duration::zero);
+        Ok((self.target_datetime, self.target_datetime + duration))
+    }
+}
+
+impl Normal {
+    pub fn parse_duration(&self) -> Result<Option<Duration>, DurationError> {
+        self.duration.as_deref().map(parse_duration).transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(s).unwrap()
+    }
+
+    #[test]
+    fn parse_fixed_length() {
+        assert_eq!(parse_duration("PT6H"), Ok(Duration::hours(6)));
+        assert_eq!(parse_duration("P1D"), Ok(Duration::days(1)));
+        assert_eq!(
+            parse_duration("P1DT3H"),
+            Ok(Duration::days(1) + Duration::hours(3))
+        );
+        assert_eq!(parse_duration("PT13H"), Ok(Duration::hours(13)));
+        assert_eq!(parse_duration("PT30M"), Ok(Duration::minutes(30)));
+        assert_eq!(parse_duration("P1W"), Ok(Duration::weeks(1)));
+        assert_eq!(parse_duration("PT1.5S"), Ok(Duration::milliseconds(1500)));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for s in [
+            "PT", "P", "PT6", "", "6H", "P1H", "PT1D", "PT3H6H", "PT6M3H", "PTH",
+        ] {
+            assert_eq!(
+                parse_duration(s),
+                Err(DurationError::Invalid(s.to_owned())),
+                "{:?}",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn parse_calendar() {
+        for s in ["P1M", "P1Y", "P1Y2M3D"] {
+            assert_eq!(
+                parse_duration(s),
+                Err(DurationError::Calendar(s.to_owned()))
+            );
+        }
+        // 時間部のMは分
+        assert_eq!(parse_duration("PT1M"), Ok(Duration::minutes(1)));
+    }
+
+    #[test]
+    fn overlaps_intervals() {
+        let t0 = at("2021-10-01T00:00:00+09:00");
+        let t6 = at("2021-10-01T06:00:00+09:00");
+        let t12 = at("2021-10-01T12:00:00+09:00");
+        assert!(overlaps((t0, t12), (t6, t12)));
+        // 終わりは含まない
+        assert!(!overlaps((t0, t6), (t6, t12)));
+        assert!(!overlaps((t6, t12), (t0, t6)));
+    }
+
+    #[test]
+    fn overlaps_zero_length() {
+        let t0 = at("2021-10-01T00:00:00+09:00");
+        let t6 = at("2021-10-01T06:00:00+09:00");
+        let t12 = at("2021-10-01T12:00:00+09:00");
+        // 始まりは含み,終わりは含まない
+        assert!(overlaps((t0, t0), (t0, t6)));
+        assert!(overlaps((t6, t12), (t6, t6)));
+        assert!(!overlaps((t6, t6), (t0, t6)));
+        assert!(!overlaps((t0, t6), (t6, t6)));
+        assert!(!overlaps((t12, t12), (t0, t6)));
+        // 長さ0同士は同じ時刻の場合のみ
+        assert!(overlaps((t6, t6), (t6, t6)));
+        assert!(!overlaps((t0, t0), (t6, t6)));
+    }
+}
@@ -0,0 +1,234 @@
+//! パース時のエラー
+
+use std::borrow::Cow;
+use std::fmt;
+
+/// 各レポート,フィードのパースで返されるエラー
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// XMLとして不正
+    Syntax(XmlError),
+    /// XMLとして正しいが想定する構造に変換できない
+    Deserialize(XmlError),
+    /// 必要な要素が存在しない
+    MissingElement { path: Cow<'static, str> },
+    /// 指定したTypeのPropertyが存在しない
+    MissingProperty { property_type: &'static str },
+    /// Propertyの個数が想定と異なる
+    UnexpectedPropertyCount {
+        path: Cow<'static, str>,
+        found: usize,
+    },
+    /// 時系列の値の個数がTimeDefineの個数と異なる
+    UnexpectedValueCount {
+        path: Cow<'static, str>,
+        expected: usize,
+        found: usize,
+    },
+    /// 要素の値が不正
+    InvalidValue {
+        path: Cow<'static, str>,
+        value: String,
+    },
+    /// 未知のMeteorologicalInfos@type
+    UnknownMeteorologicalInfosType(String),
+    /// 同じ区域に対する同種の予報が重複している
+    DuplicatedAreaForecast { code: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Syntax(e) => write!(f, "invalid xml: {}", e),
+            ParseError::Deserialize(e) => write!(f, "unexpected structure: {}", e),
+            ParseError::MissingElement { path } => write!(f, "missing element {}", path),
+            ParseError::MissingProperty { property_type } => {
+                write!(f, "missing Property with Type {}", property_type)
+            }
+            ParseError::UnexpectedPropertyCount { path, found } => {
+                write!(f, "invalid count of property at {}: {}", path, found)
+            }
+            ParseError::UnexpectedValueCount {
+                path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "invalid count of values at {}: expected {}, found {}",
+                path, expected, found
+            ),
+            ParseError::InvalidValue { path, value } => {
+                write!(f, "invalid value at {}: {}", path, value)
+            }
+            ParseError::UnknownMeteorologicalInfosType(t) => {
+                write!(f, "unknown MeteorologicalInfos@type {}", t)
+            }
+            ParseError::DuplicatedAreaForecast { code } => {
+                write!(f, "duplicated forecast item for area {}", code)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseError::Syntax(e) | ParseError::Deserialize(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// XMLの読み込み,変換で発生したエラー
+#[derive(Debug)]
+pub struct XmlError(pub(crate) crate::de::Error);
+
+impl fmt::Display for XmlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for XmlError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        std::error::Error::source(&self.0)
+    }
+}
+
+/// ISO 8601の期間のパースで返されるエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DurationError {
+    /// ISO 8601の期間として不正
+    Invalid(String),
+    /// 年,月を含み長さが一定でない
+    Calendar(String),
+}
+
+impl fmt::Display for DurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DurationError::Invalid(s) => write!(f, "invalid ISO 8601 duration: {}", s),
+            DurationError::Calendar(s) => {
+                write!(
+                    f,
+                    "duration with years or months is not fixed length: {}",
+                    s
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for DurationError {}
+
+/// 地域コード表の読み込みで返されるエラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RegistryError {
+    /// 行の形式が不正
+    InvalidLine { line: usize, reason: String },
+    /// 同じ種別とコードの行が重複している
+    Duplicated { kind: &'static str, code: usize },
+    /// 親の区域が存在しない
+    UnknownParent { code: usize, parent: usize },
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::InvalidLine { line, reason } => {
+                write!(f, "invalid area code table at line {}: {}", line, reason)
+            }
+            RegistryError::Duplicated { kind, code } => {
+                write!(f, "duplicated area code {} {}", kind, code)
+            }
+            RegistryError::UnknownParent { code, parent } => {
+                write!(f, "unknown parent {} of area code {}", parent, code)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::feed::{EntryReader, Feed};
+    use crate::fuken_r1::Report;
+
+    use super::*;
+
+    const FEED: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom" lang="ja">
+<title>高頻度（定時）</title><subtitle>JMAXML publishing feed</subtitle>
+<updated>2021-10-01T02:00:00+09:00</updated><id>urn:uuid:feed</id>
+<link rel="self" href="https://www.data.jma.go.jp/developer/xml/feed/regular.xml"/>
+<rights type="html">rights</rights>
+<entry><title>府県天気予報（Ｒ１）</title><id>urn:uuid:entry</id>
+<updated>2021-10-01T01:59:19Z</updated><author><name>気象庁</name></author>
+<link type="application/xml" href="https://www.data.jma.go.jp/developer/xml/data/r1.xml"/>
+<content type="text">【府県天気予報】</content></entry>
+</feed>"#;
+    const R1: &str = include_str!("../benches/data/r1.xml");
+
+    fn missing_element(e: ParseError) -> String {
+        match e {
+            ParseError::MissingElement { path } => path.into_owned(),
+            e => panic!("unexpected error {:?}", e),
+        }
+    }
+
+    fn invalid_value(e: ParseError) -> (String, String) {
+        match e {
+            ParseError::InvalidValue { path, value } => (path.into_owned(), value),
+            e => panic!("unexpected error {:?}", e),
+        }
+    }
+
+    #[test]
+    fn missing_element_in_feed() {
+        let feed = FEED.replacen("<id>urn:uuid:feed</id>", "", 1);
+        assert_eq!(missing_element(feed.parse::<Feed>().unwrap_err()), "id");
+
+        let feed = FEED.replacen("<id>urn:uuid:entry</id>", "", 1);
+        assert_eq!(
+            missing_element(feed.parse::<Feed>().unwrap_err()),
+            "entry/id"
+        );
+        let entry = EntryReader::new(feed.as_bytes()).next().unwrap();
+        assert_eq!(missing_element(entry.unwrap_err()), "entry/id");
+    }
+
+    #[test]
+    fn invalid_value_in_report() {
+        let report = R1.replacen("<Status>通常</Status>", "<Status>不明</Status>", 1);
+        assert_eq!(
+            invalid_value(report.parse::<Report>().unwrap_err()),
+            ("Control/Status".to_owned(), "不明".to_owned())
+        );
+
+        let report = R1.replacen("<InfoType>発表</InfoType>", "<InfoType>不明</InfoType>", 1);
+        assert_eq!(
+            invalid_value(report.parse::<Report>().unwrap_err()),
+            ("Head/InfoType".to_owned(), "不明".to_owned())
+        );
+
+        let feed = FEED.replacen("2021-10-01T01:59:19Z", "yesterday", 1);
+        assert_eq!(
+            invalid_value(feed.parse::<Feed>().unwrap_err()),
+            ("entry/updated".to_owned(), "yesterday".to_owned())
+        );
+    }
+
+    #[test]
+    fn syntax_error() {
+        for xml in [&FEED[..FEED.len() - 10], "", "<feed><entry></feed>"] {
+            match xml.parse::<Feed>() {
+                Err(ParseError::Syntax(_)) => {}
+                r => panic!("unexpected result {:?}", r),
+            }
+        }
+    }
+}
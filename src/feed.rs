@@ -1,46 +1,62 @@
 use std::collections::{HashMap, HashSet};
 
-use chrono::{DateTime, Local};
+use chrono::{DateTime, FixedOffset};
 use serde_derive::Deserialize;
+#[cfg(feature = "serialize")]
+use serde_derive::Serialize;
+use std::io::BufRead;
 use std::str::FromStr;
 
+use crate::de;
+use crate::document::{parse_document, JmaDocument};
+use crate::error::ParseError;
+use crate::fetch::{AsyncDocumentFetcher, DocumentFetcher, FetchError};
+
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct Feed {
     pub lang: String,
     pub title: String,
     pub subtitle: String,
-    pub updated: DateTime<Local>,
+    pub updated: DateTime<FixedOffset>,
     pub id: String,
     pub link: Vec<Link>,
     pub rights: Rights,
-    #[serde(rename = "entry")]
+    #[serde(
+        rename(deserialize = "entry", serialize = "entries"),
+        alias = "entries"
+    )]
     pub entries: Vec<Entry>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct Entry {
     pub title: String,
     pub id: String,
-    pub updated: DateTime<Local>,
+    pub updated: DateTime<FixedOffset>,
     pub author: Author,
     pub link: Link,
     pub content: Content,
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct Author {
     pub name: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct Content {
     #[serde(rename = "type")]
     pub _type: String,
-    #[serde(rename = "$value")]
+    #[serde(rename(deserialize = "$value", serialize = "value"), alias = "value")]
     pub value: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct Link {
     #[serde(rename = "type")]
     pub _type: Option<String>,
@@ -49,13 +65,33 @@ pub struct Link {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct Rights {
     #[serde(rename = "type")]
     pub _type: String,
-    #[serde(rename = "$value")]
+    #[serde(rename(deserialize = "$value", serialize = "item"), alias = "item")]
     pub item: String,
 }
 
+impl Entry {
+    /// リンク先の電文を取得して種類に応じてパースする
+    pub fn fetch_and_parse<F: DocumentFetcher + ?Sized>(
+        &self,
+        fetcher: &F,
+    ) -> Result<JmaDocument, FetchError> {
+        let xml = fetcher.fetch(&self.link.href)?;
+        Ok(parse_document(&xml)?)
+    }
+
+    pub async fn fetch_and_parse_async<F: AsyncDocumentFetcher + ?Sized>(
+        &self,
+        fetcher: &F,
+    ) -> Result<JmaDocument, FetchError> {
+        let xml = fetcher.fetch(&self.link.href).await?;
+        Ok(parse_document(&xml)?)
+    }
+}
+
 impl Feed {
     pub fn into_titled_entries<S: AsRef<str>>(
         self,
@@ -77,9 +113,43 @@ impl Feed {
 }
 
 impl FromStr for Feed {
-    type Err = serde_xml_rs::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        serde_xml_rs::from_str(s)
+        de::from_str(s)
+    }
+}
+
+impl Feed {
+    /// 読み込み元から直接パースする
+    ///
+    /// 省けるのは入力全体を`String`に読み込むことだけで,フィード全体の要素の木と
+    /// 全てのEntryはメモリ上に作られる。大きなフィードを順に読むには[`EntryReader`]を使う
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, ParseError> {
+        de::from_reader(reader)
+    }
+}
+
+/// フィードの`entry`を1件ずつ読み出す
+///
+/// [`Feed`]を組み立てずに読み進めるため,長期フィード(`_l`)のような大きなフィードでも
+/// 保持するのは読み出し中の1件分だけで済む。`feed`直下の`entry`以外は読み飛ばす。
+pub struct EntryReader<R> {
+    children: de::ChildReader<R>,
+}
+
+impl<R: BufRead> EntryReader<R> {
+    pub fn new(reader: R) -> Self {
+        EntryReader {
+            children: de::ChildReader::new(reader),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for EntryReader<R> {
+    type Item = Result<Entry, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.children.next("entry")
     }
 }
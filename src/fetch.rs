@@ -0,0 +1,481 @@
+//! フィードのリンク先の電文の取得
+
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::path::PathBuf;
+use std::pin::Pin;
+
+use crate::error::ParseError;
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// URLから電文のXMLを取得する
+pub trait DocumentFetcher {
+    fn fetch(&self, url: &str) -> Result<String, FetchError>;
+}
+
+/// URLから電文のXMLを非同期に取得する
+pub trait AsyncDocumentFetcher {
+    fn fetch<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<String, FetchError>>;
+}
+
+/// 条件付きでフィードを取得する
+pub trait FeedFetcher {
+    fn fetch_feed(&self, request: &FeedRequest<'_>) -> Result<FeedResponse, FetchError>;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FeedRequest<'a> {
+    pub url: &'a str,
+    /// 前回のレスポンスのETag
+    pub etag: Option<&'a str>,
+    /// 前回のレスポンスのLast-Modified
+    pub last_modified: Option<&'a str>,
+}
+
+#[derive(Debug, Clone)]
+pub enum FeedResponse {
+    NotModified,
+    Modified {
+        body: String,
+        etag: Option<String>,
+        last_modified: Option<String>,
+    },
+}
+
+/// 電文の取得,パースで返されるエラー
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum FetchError {
+    Io(io::Error),
+    /// 取得元に対応付けられないURL
+    InvalidUrl(String),
+    #[cfg(feature = "http")]
+    Http(reqwest::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Io(e) => write!(f, "io error: {}", e),
+            FetchError::InvalidUrl(url) => write!(f, "invalid url: {}", url),
+            #[cfg(feature = "http")]
+            FetchError::Http(e) => write!(f, "http error: {}", e),
+            FetchError::Parse(e) => write!(f, "parse error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FetchError::Io(e) => Some(e),
+            FetchError::InvalidUrl(_) => None,
+            #[cfg(feature = "http")]
+            FetchError::Http(e) => Some(e),
+            FetchError::Parse(e) => Some(e),
+        }
+    }
+}
+
+impl From<io::Error> for FetchError {
+    fn from(e: io::Error) -> Self {
+        FetchError::Io(e)
+    }
+}
+
+impl From<ParseError> for FetchError {
+    fn from(e: ParseError) -> Self {
+        FetchError::Parse(e)
+    }
+}
+
+#[cfg(feature = "http")]
+impl From<reqwest::Error> for FetchError {
+    fn from(e: reqwest::Error) -> Self {
+        FetchError::Http(e)
+    }
+}
+
+/// URLの末尾のファイル名でディレクトリ内のファイルを読む
+///
+/// クエリ,フラグメントとディレクトリ部分は無視するため,ディレクトリの外は読まない
+#[derive(Debug, Clone)]
+pub struct FileSystemFetcher {
+    root: PathBuf,
+}
+
+impl FileSystemFetcher {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        FileSystemFetcher { root: root.into() }
+    }
+
+    fn path(&self, url: &str) -> Result<PathBuf, FetchError> {
+        let path = url.split(['?', '#']).next().unwrap_or_default();
+        match path.rsplit(['/', '\\']).next() {
+            Some(name) if !name.is_empty() && name != "." && name != ".." => {
+                Ok(self.root.join(name))
+            }
+            _ => Err(FetchError::InvalidUrl(url.to_owned())),
+        }
+    }
+}
+
+impl DocumentFetcher for FileSystemFetcher {
+    fn fetch(&self, url: &str) -> Result<String, FetchError> {
+        Ok(std::fs::read_to_string(self.path(url)?)?)
+    }
+}
+
+/// ファイルの読み込みはブロッキングで行われる
+impl AsyncDocumentFetcher for FileSystemFetcher {
+    fn fetch<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<String, FetchError>> {
+        Box::pin(async move { DocumentFetcher::fetch(self, url) })
+    }
+}
+
+/// 常に全体を読み込む
+impl FeedFetcher for FileSystemFetcher {
+    fn fetch_feed(&self, request: &FeedRequest<'_>) -> Result<FeedResponse, FetchError> {
+        Ok(FeedResponse::Modified {
+            body: DocumentFetcher::fetch(self, request.url)?,
+            etag: None,
+            last_modified: None,
+        })
+    }
+}
+
+#[cfg(feature = "http")]
+pub use http::{AsyncHttpFetcher, HttpFetcher};
+
+#[cfg(feature = "http")]
+mod http {
+    use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+    use reqwest::StatusCode;
+
+    use super::{
+        AsyncDocumentFetcher, BoxFuture, DocumentFetcher, FeedFetcher, FeedRequest, FeedResponse,
+        FetchError,
+    };
+
+    /// reqwestのブロッキングクライアントによる取得
+    #[derive(Debug, Clone, Default)]
+    pub struct HttpFetcher {
+        client: reqwest::blocking::Client,
+    }
+
+    impl HttpFetcher {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn with_client(client: reqwest::blocking::Client) -> Self {
+            HttpFetcher { client }
+        }
+
+        /// 前回のETag,Last-Modifiedを条件にしたリクエスト
+        pub(super) fn feed_request(
+            &self,
+            request: &FeedRequest<'_>,
+        ) -> reqwest::blocking::RequestBuilder {
+            let mut builder = self.client.get(request.url);
+            if let Some(etag) = request.etag {
+                builder = builder.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = request.last_modified {
+                builder = builder.header(IF_MODIFIED_SINCE, last_modified);
+            }
+            builder
+        }
+    }
+
+    impl DocumentFetcher for HttpFetcher {
+        fn fetch(&self, url: &str) -> Result<String, FetchError> {
+            Ok(self.client.get(url).send()?.error_for_status()?.text()?)
+        }
+    }
+
+    impl FeedFetcher for HttpFetcher {
+        fn fetch_feed(&self, request: &FeedRequest<'_>) -> Result<FeedResponse, FetchError> {
+            let response = self.feed_request(request).send()?;
+            if response.status() == StatusCode::NOT_MODIFIED {
+                return Ok(FeedResponse::NotModified);
+            }
+            let response = response.error_for_status()?;
+            let header = |name| {
+                response
+                    .headers()
+                    .get(name)
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_owned)
+            };
+            let etag = header(ETAG);
+            let last_modified = header(LAST_MODIFIED);
+            Ok(FeedResponse::Modified {
+                body: response.text()?,
+                etag,
+                last_modified,
+            })
+        }
+    }
+
+    /// reqwestの非同期クライアントによる取得
+    #[derive(Debug, Clone, Default)]
+    pub struct AsyncHttpFetcher {
+        client: reqwest::Client,
+    }
+
+    impl AsyncHttpFetcher {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn with_client(client: reqwest::Client) -> Self {
+            AsyncHttpFetcher { client }
+        }
+    }
+
+    impl AsyncDocumentFetcher for AsyncHttpFetcher {
+        fn fetch<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<String, FetchError>> {
+            Box::pin(async move {
+                Ok(self
+                    .client
+                    .get(url)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .text()
+                    .await?)
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+    use std::path::Path;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    use chrono::DateTime;
+
+    use super::*;
+    use crate::document::JmaDocument;
+    use crate::feed::{Author, Content, Entry, Link};
+
+    fn fetcher() -> FileSystemFetcher {
+        FileSystemFetcher::new(concat!(env!("CARGO_MANIFEST_DIR"), "/benches/data"))
+    }
+
+    fn entry(href: &str) -> Entry {
+        Entry {
+            title: "府県天気予報（Ｒ１）".to_owned(),
+            id: "urn:uuid:entry".to_owned(),
+            updated: DateTime::parse_from_rfc3339("2021-10-01T01:59:19Z").unwrap(),
+            author: Author {
+                name: "気象庁".to_owned(),
+            },
+            link: Link {
+                _type: Some("application/xml".to_owned()),
+                rel: None,
+                href: href.to_owned(),
+            },
+            content: Content {
+                _type: "text".to_owned(),
+                value: String::new(),
+            },
+        }
+    }
+
+    /// 即座に完了するFutureを実行する
+    fn ready<F: Future>(future: F) -> F::Output {
+        match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("future is not ready"),
+        }
+    }
+
+    fn kind(document: &JmaDocument) -> &'static str {
+        match document {
+            JmaDocument::FukenR1(_) => "FukenR1",
+            JmaDocument::Shukan(_) => "Shukan",
+            JmaDocument::KeihoH27(_) => "KeihoH27",
+            JmaDocument::ShingenShindo(_) => "ShingenShindo",
+            JmaDocument::Tsunami(_) => "Tsunami",
+            JmaDocument::Unsupported { .. } => "Unsupported",
+        }
+    }
+
+    #[test]
+    fn fetch_and_parse_from_directory() {
+        let fetcher = fetcher();
+        let base = "https://www.data.jma.go.jp/developer/xml/data/";
+        for (name, expected) in [
+            ("r1.xml", "FukenR1"),
+            ("shukan.xml", "Shukan"),
+            ("keiho.xml", "KeihoH27"),
+            ("vxse53.xml", "ShingenShindo"),
+            ("vtse41.xml", "Tsunami"),
+        ] {
+            let entry = entry(&format!("{}{}", base, name));
+            let document = entry.fetch_and_parse(&fetcher).unwrap();
+            assert_eq!(kind(&document), expected, "{}", name);
+            let document = ready(entry.fetch_and_parse_async(&fetcher)).unwrap();
+            assert_eq!(kind(&document), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn fetch_missing_file() {
+        let entry = entry("https://www.data.jma.go.jp/developer/xml/data/missing.xml");
+        assert!(matches!(
+            entry.fetch_and_parse(&fetcher()),
+            Err(FetchError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn sanitize_url() {
+        let fetcher = fetcher();
+        let root = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/benches/data"));
+        for url in [
+            "https://www.data.jma.go.jp/developer/xml/data/r1.xml",
+            "https://www.data.jma.go.jp/developer/xml/data/r1.xml?t=1#top",
+            "https://www.data.jma.go.jp/developer/xml/../../r1.xml",
+            "../../r1.xml",
+            "..\\..\\r1.xml",
+            "r1.xml",
+        ] {
+            assert_eq!(fetcher.path(url).unwrap(), root.join("r1.xml"), "{}", url);
+        }
+        for url in [
+            "https://www.data.jma.go.jp/developer/xml/data/",
+            "https://www.data.jma.go.jp/developer/xml/data/..",
+            "https://www.data.jma.go.jp/developer/xml/data/.?r1.xml",
+            "..\\",
+            "",
+        ] {
+            assert!(
+                matches!(fetcher.path(url), Err(FetchError::InvalidUrl(_))),
+                "{}",
+                url
+            );
+        }
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn conditional_feed_request() {
+        use reqwest::header::{IF_MODIFIED_SINCE, IF_NONE_MATCH};
+
+        let fetcher = HttpFetcher::new();
+        let url = "https://www.data.jma.go.jp/developer/xml/feed/regular.xml";
+        let request = fetcher
+            .feed_request(&FeedRequest {
+                url,
+                etag: Some("\"abc\""),
+                last_modified: Some("Fri, 01 Oct 2021 02:00:00 GMT"),
+            })
+            .build()
+            .unwrap();
+        assert_eq!(request.method(), reqwest::Method::GET);
+        assert_eq!(request.url().as_str(), url);
+        assert_eq!(request.headers()[IF_NONE_MATCH], "\"abc\"");
+        assert_eq!(
+            request.headers()[IF_MODIFIED_SINCE],
+            "Fri, 01 Oct 2021 02:00:00 GMT"
+        );
+
+        // 初回は条件なし
+        let request = fetcher
+            .feed_request(&FeedRequest {
+                url,
+                etag: None,
+                last_modified: None,
+            })
+            .build()
+            .unwrap();
+        assert!(request.headers().get(IF_NONE_MATCH).is_none());
+        assert!(request.headers().get(IF_MODIFIED_SINCE).is_none());
+
+        let invalid = fetcher
+            .feed_request(&FeedRequest {
+                url: "not a url",
+                etag: None,
+                last_modified: None,
+            })
+            .build();
+        assert!(invalid.is_err());
+    }
+
+    /// ループバックで1回だけ応答するサーバーから取得する
+    #[cfg(feature = "http")]
+    #[test]
+    fn fetch_feed_over_http() {
+        use std::io::{BufRead, BufReader, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/feed.xml", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in [
+                "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nLast-Modified: Fri, 01 Oct 2021 02:00:00 GMT\r\nContent-Length: 4\r\nConnection: close\r\n\r\nbody",
+                "HTTP/1.1 304 Not Modified\r\nConnection: close\r\n\r\n",
+            ] {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                    request.push_str(&line.to_ascii_lowercase());
+                }
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+                requests.push(request);
+            }
+            requests
+        });
+
+        let fetcher = HttpFetcher::new();
+        let first = fetcher
+            .fetch_feed(&FeedRequest {
+                url: &url,
+                etag: None,
+                last_modified: None,
+            })
+            .unwrap();
+        let (etag, last_modified) = match first {
+            FeedResponse::Modified {
+                body,
+                etag,
+                last_modified,
+            } => {
+                assert_eq!(body, "body");
+                (etag.unwrap(), last_modified.unwrap())
+            }
+            r => panic!("{:?}", r),
+        };
+        assert_eq!(etag, "\"v1\"");
+        let second = fetcher
+            .fetch_feed(&FeedRequest {
+                url: &url,
+                etag: Some(&etag),
+                last_modified: Some(&last_modified),
+            })
+            .unwrap();
+        assert!(matches!(second, FeedResponse::NotModified));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("get /feed.xml "));
+        assert!(!requests[0].contains("if-none-match"));
+        assert!(requests[1].contains("if-none-match: \"v1\"\r\n"));
+        assert!(requests[1].contains("if-modified-since: fri, 01 oct 2021 02:00:00 gmt\r\n"));
+    }
+}
@@ -2,39 +2,45 @@
 
 use std::convert::TryInto;
 
-use chrono::{DateTime, Local};
+use chrono::{DateTime, FixedOffset, NaiveDate};
 use serde_derive::Deserialize;
+#[cfg(feature = "serialize")]
+use serde_derive::Serialize;
 use std::collections::HashMap;
+use std::io::BufRead;
 use std::str::FromStr;
 
+use crate::de;
+use crate::error::ParseError;
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Report {
     pub control: Control,
     pub head: Head,
-    pub body: Vec<MeteorologicalInfoList>,
+    /// 付加事項
+    pub addition: Option<Addition>,
+    /// 一次細分区域毎の予報,電文中に最初に現れた順
+    pub areas: Vec<MeteorologicalInfoList>,
+    /// 気温予報地点毎の予報,電文中に最初に現れた順
+    pub stations: Vec<StationInfoList>,
     pub proprietary_forecasts: Vec<MeteorologicalInfo>,
 }
 
 impl Report {
-    fn from_tmp(tmp: ReportTmp) -> anyhow::Result<Self> {
+    fn from_tmp(tmp: ReportTmp) -> Result<Self, ParseError> {
         let ReportTmp {
             control,
             head,
+            addition,
             body: BodyTmp {
                 meteorological_infos,
             },
         } = tmp;
-        let mut body_map = HashMap::<
-            usize,
-            (
-                Area,
-                Option<Vec<WeatherForecast>>,
-                Option<Vec<Precipitation>>,
-                Option<Vec<WeatherAndWindForecast>>,
-                Option<Vec<TemperatureForecast>>,
-                Option<Vec<TemperatureTimeSeries>>,
-            ),
-        >::new();
+        let mut areas = Vec::<MeteorologicalInfoList>::new();
+        let mut area_index = HashMap::<usize, usize>::new();
+        let mut stations = Vec::<StationInfoList>::new();
+        let mut station_index = HashMap::<usize, usize>::new();
         let mut proprietary_forecasts = Vec::new();
         for i in meteorological_infos
             .into_iter()
@@ -44,66 +50,61 @@ impl Report {
                 match info {
                     MeteorologicalInfoListItem::WeatherForecast(list) => {
                         for AreaForecast { area, forecast } in list {
-                            if body_map
-                                .entry(area.code)
-                                .or_insert((area, None, None, None, None, None))
-                                .1
+                            let code = area.code;
+                            if area_list_of(&mut areas, &mut area_index, area)
+                                .weather_forecast
                                 .replace(forecast)
                                 .is_some()
                             {
-                                return Err(anyhow::Error::msg("duplicated forecast item"));
+                                return Err(ParseError::DuplicatedAreaForecast { code });
                             }
                         }
                     }
                     MeteorologicalInfoListItem::ProbabilityOfPrecipitation(list) => {
                         for AreaForecast { area, forecast } in list {
-                            if body_map
-                                .entry(area.code)
-                                .or_insert((area, None, None, None, None, None))
-                                .2
+                            let code = area.code;
+                            if area_list_of(&mut areas, &mut area_index, area)
+                                .probability_of_precipitation
                                 .replace(forecast)
                                 .is_some()
                             {
-                                return Err(anyhow::Error::msg("duplicated forecast item"));
+                                return Err(ParseError::DuplicatedAreaForecast { code });
                             }
                         }
                     }
                     MeteorologicalInfoListItem::WeatherAndWindTimeSeries(list) => {
                         for AreaForecast { area, forecast } in list {
-                            if body_map
-                                .entry(area.code)
-                                .or_insert((area, None, None, None, None, None))
-                                .3
+                            let code = area.code;
+                            if area_list_of(&mut areas, &mut area_index, area)
+                                .weather_and_wind_time_series
                                 .replace(forecast)
                                 .is_some()
                             {
-                                return Err(anyhow::Error::msg("duplicated forecast item"));
+                                return Err(ParseError::DuplicatedAreaForecast { code });
                             }
                         }
                     }
                     MeteorologicalInfoListItem::TemperatureForecast(list) => {
-                        for AreaForecast { area, forecast } in list {
-                            if body_map
-                                .entry(area.code)
-                                .or_insert((area, None, None, None, None, None))
-                                .4
+                        for StationForecast { station, forecast } in list {
+                            let code = station.code;
+                            if station_list_of(&mut stations, &mut station_index, station)
+                                .temperature_forecast
                                 .replace(forecast)
                                 .is_some()
                             {
-                                return Err(anyhow::Error::msg("duplicated forecast item"));
+                                return Err(ParseError::DuplicatedAreaForecast { code });
                             }
                         }
                     }
                     MeteorologicalInfoListItem::TemperatureTimeSeries(list) => {
-                        for AreaForecast { area, forecast } in list {
-                            if body_map
-                                .entry(area.code)
-                                .or_insert((area, None, None, None, None, None))
-                                .5
+                        for StationForecast { station, forecast } in list {
+                            let code = station.code;
+                            if station_list_of(&mut stations, &mut station_index, station)
+                                .temperature_time_series
                                 .replace(forecast)
                                 .is_some()
                             {
-                                return Err(anyhow::Error::msg("duplicated forecast item"));
+                                return Err(ParseError::DuplicatedAreaForecast { code });
                             }
                         }
                     }
@@ -113,47 +114,109 @@ impl Report {
                 }
             }
         }
-        let mut body = Vec::with_capacity(body_map.len());
-        for (
-            _,
-            (
-                area,
-                weather_forecast,
-                probability_of_precipitation,
-                weather_and_wind_time_series,
-                temperature_forecast,
-                temperature_time_series,
-            ),
-        ) in body_map
-        {
-            body.push(MeteorologicalInfoList {
-                area,
-                weather_forecast,
-                probability_of_precipitation,
-                weather_and_wind_time_series,
-                temperature_forecast,
-                temperature_time_series,
-            });
+        // 地点予報の地点は区域予報の区域と同じ順に1地点ずつ並ぶため,数が合えば電文での対応を優先し,
+        // 合わなければ同梱のコード表での対応を使う
+        if stations.len() == areas.len() {
+            for (station, area) in stations.iter_mut().zip(&areas) {
+                station.area_code = Some(area.area.code);
+            }
         }
         Ok(Report {
             control,
             head,
-            body,
+            addition,
+            areas,
+            stations,
             proprietary_forecasts,
         })
     }
 }
 
+/// 区域毎のMeteorologicalInfoList,なければ末尾に追加する
+fn area_list_of<'a>(
+    areas: &'a mut Vec<MeteorologicalInfoList>,
+    area_index: &mut HashMap<usize, usize>,
+    area: Area,
+) -> &'a mut MeteorologicalInfoList {
+    let i = *area_index.entry(area.code).or_insert_with(|| {
+        areas.push(MeteorologicalInfoList {
+            area,
+            weather_forecast: None,
+            probability_of_precipitation: None,
+            weather_and_wind_time_series: None,
+        });
+        areas.len() - 1
+    });
+    &mut areas[i]
+}
+
+/// 地点毎のStationInfoList,なければ末尾に追加する
+fn station_list_of<'a>(
+    stations: &'a mut Vec<StationInfoList>,
+    station_index: &mut HashMap<usize, usize>,
+    station: Station,
+) -> &'a mut StationInfoList {
+    let i = *station_index.entry(station.code).or_insert_with(|| {
+        stations.push(StationInfoList {
+            area_code: station.area_code(),
+            station,
+            temperature_forecast: None,
+            temperature_time_series: None,
+        });
+        stations.len() - 1
+    });
+    &mut stations[i]
+}
+
+impl Report {
+    pub fn area(&self, code: usize) -> Option<&MeteorologicalInfoList> {
+        self.areas.iter().find(|list| list.area.code == code)
+    }
+
+    pub fn station(&self, code: usize) -> Option<&StationInfoList> {
+        self.stations.iter().find(|list| list.station.code == code)
+    }
+
+    /// 地点が属する区域
+    pub fn area_of_station(&self, code: usize) -> Option<&MeteorologicalInfoList> {
+        self.area(self.station(code)?.area_code?)
+    }
+
+    /// 区域に属する地点
+    pub fn stations_in_area(&self, code: usize) -> impl Iterator<Item = &StationInfoList> {
+        self.stations
+            .iter()
+            .filter(move |list| list.area_code == Some(code))
+    }
+}
+
 impl FromStr for Report {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let tmp: ReportTmp = serde_xml_rs::from_str(s)?;
+        let tmp: ReportTmp = de::from_str(s)?;
+        Report::from_tmp(tmp)
+    }
+}
+
+impl Report {
+    /// 読み込み元から直接パースする
+    ///
+    /// 省けるのは入力全体を`String`に読み込むことだけで,
+    /// 電文全体の要素の木は[`FromStr`]と同じくメモリ上に作られる
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, ParseError> {
+        let tmp: ReportTmp = de::from_reader(reader)?;
         Report::from_tmp(tmp)
     }
+
+    /// 読み込み済みの要素の木からパースする
+    pub(crate) fn from_document(document: &de::Document) -> Result<Self, ParseError> {
+        Report::from_tmp(document.deserialize()?)
+    }
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct MeteorologicalInfoList {
     pub area: Area,
     /// 今日,明日,(明後日)の天気,風,波
@@ -162,6 +225,17 @@ pub struct MeteorologicalInfoList {
     pub probability_of_precipitation: Option<Vec<Precipitation>>,
     /// 3時間毎の天気,風
     pub weather_and_wind_time_series: Option<Vec<WeatherAndWindForecast>>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct StationInfoList {
+    pub station: Station,
+    /// 地点が属する一次細分区域
+    ///
+    /// 区域と地点の数が等しい電文では同じ順の区域,そうでなければ同梱のコード表での親の区域で,
+    /// どちらでも分からなければNone
+    pub area_code: Option<usize>,
     /// 向こう数日の気温
     pub temperature_forecast: Option<Vec<TemperatureForecast>>,
     /// 3時間毎の気温
@@ -177,24 +251,28 @@ enum MeteorologicalInfoListItem {
     /// 3時間毎の天気,風
     WeatherAndWindTimeSeries(Vec<AreaForecast<WeatherAndWindForecast>>),
     /// 向こう数日の気温
-    TemperatureForecast(Vec<AreaForecast<TemperatureForecast>>),
+    TemperatureForecast(Vec<StationForecast<TemperatureForecast>>),
     /// 3時間毎の気温
-    TemperatureTimeSeries(Vec<AreaForecast<TemperatureTimeSeries>>),
+    TemperatureTimeSeries(Vec<StationForecast<TemperatureTimeSeries>>),
     /// 独自予報
     Proprietary(Vec<MeteorologicalInfo>),
 }
 
 impl MeteorologicalInfoListItem {
-    fn from_tmp(tmp: MeteorologicalInfosTmp) -> anyhow::Result<Vec<Self>> {
+    fn from_tmp(tmp: MeteorologicalInfosTmp) -> Result<Vec<Self>, ParseError> {
         let MeteorologicalInfosTmp {
             _type,
             time_series_info,
             meteorological_info,
         } = tmp;
-        if _type == "独自予報" {
-            return Ok(vec![MeteorologicalInfoListItem::Proprietary(
-                meteorological_info,
-            )]);
+        match _type.as_str() {
+            "独自予報" => {
+                return Ok(vec![MeteorologicalInfoListItem::Proprietary(
+                    meteorological_info,
+                )]);
+            }
+            "区域予報" | "地点予報" => {}
+            _ => return Err(ParseError::UnknownMeteorologicalInfosType(_type)),
         }
         let mut result = Vec::new();
         for time_series_info in time_series_info {
@@ -206,288 +284,55 @@ impl MeteorologicalInfoListItem {
                 items,
             } = time_series_info;
             time_defines.sort_by_key(|t| t.time_id);
-            match _type.as_str() {
-                "区域予報" => {
-                    match items[0].kind[0].property[0]._type.as_str() {
-                        "天気" | "波" | "風" => {
-                            let mut forecasts = Vec::new();
-                            for Item { kind, area } in items {
-                                let properties = kind
-                                    .into_iter()
-                                    .flat_map(|kind| kind.property)
-                                    .collect::<Vec<_>>();
-                                match properties.len() {
-                                    3 => {
-                                        let mut properties: [_; 3] = properties.try_into().unwrap();
-                                        properties.sort_by_key(|p| p._type.clone());
-                                        if let [Property {
-                                            _type: weather_type,
-                                            detail_forecast:
-                                                Some(DetailForecast {
-                                                    mut weather_forecast_part,
-                                                    ..
-                                                }),
-                                            weather_part: Some(WeatherPart { mut weather }),
-                                            weather_code_part:
-                                                Some(WeatherCodePart { mut weather_code }),
-                                            ..
-                                        }, Property {
-                                            _type: wave_height_type,
-                                            detail_forecast:
-                                                Some(DetailForecast {
-                                                    mut wave_height_forecast_part,
-                                                    ..
-                                                }),
-                                            ..
-                                        }, Property {
-                                            _type: wind_type,
-                                            detail_forecast:
-                                                Some(DetailForecast {
-                                                    mut wind_forecast_part,
-                                                    ..
-                                                }),
-                                            ..
-                                        }] = properties
-                                        {
-                                            assert_eq!(weather_type, "天気");
-                                            assert_eq!(wave_height_type, "波");
-                                            assert_eq!(wind_type, "風");
-                                            weather_forecast_part.sort_by_key(|w| w.ref_id);
-                                            weather.sort_by_key(|w| w.ref_id);
-                                            weather_code.sort_by_key(|w| w.ref_id);
-                                            wave_height_forecast_part.sort_by_key(|w| w.ref_id);
-                                            wind_forecast_part.sort_by_key(|w| w.ref_id);
-                                            let forecast = time_defines.iter().zip(weather_forecast_part).zip(weather).zip(weather_code).zip(wave_height_forecast_part).zip(wind_forecast_part)
-                                                .map(|(((((time_define, weather_text), weather), weather_code), wave_height), wind)| WeatherForecast { time: time_define.clone(), weather_text, weather, weather_code, wind, wave_height: Some(wave_height) })
-                                                .collect();
-                                            forecasts.push(AreaForecast { area, forecast });
-                                        } else {
-                                            return Err(anyhow::Error::msg(
-                                                "Property has no some property",
-                                            ));
-                                        }
-                                    }
-                                    2 => {
-                                        let mut properties: [_; 2] =
-                                            properties.try_into().map_err(|_| {
-                                                anyhow::Error::msg("invalid count of property")
-                                            })?;
-                                        properties.sort_by_key(|p| p._type.clone());
-                                        if let [Property {
-                                            _type: weather_type,
-                                            detail_forecast:
-                                                Some(DetailForecast {
-                                                    mut weather_forecast_part,
-                                                    ..
-                                                }),
-                                            weather_part: Some(WeatherPart { mut weather }),
-                                            weather_code_part:
-                                                Some(WeatherCodePart { mut weather_code }),
-                                            ..
-                                        }, Property {
-                                            _type: wind_type,
-                                            detail_forecast:
-                                                Some(DetailForecast {
-                                                    mut wind_forecast_part,
-                                                    ..
-                                                }),
-                                            ..
-                                        }] = properties
-                                        {
-                                            assert_eq!(weather_type, "天気");
-                                            assert_eq!(wind_type, "風");
-                                            weather_forecast_part.sort_by_key(|w| w.ref_id);
-                                            weather.sort_by_key(|w| w.ref_id);
-                                            weather_code.sort_by_key(|w| w.ref_id);
-                                            wind_forecast_part.sort_by_key(|w| w.ref_id);
-                                            let forecast = time_defines.iter().zip(weather_forecast_part).zip(weather).zip(weather_code).zip(wind_forecast_part)
-                                                .map(|((((time_define, weather_text), weather), weather_code), wind)| WeatherForecast { time: time_define.clone(), weather_text, weather, weather_code, wind, wave_height: None })
-                                                .collect();
-                                            forecasts.push(AreaForecast { area, forecast });
-                                        } else {
-                                            return Err(anyhow::Error::msg(
-                                                "Property has no some property",
-                                            ));
-                                        }
-                                    }
-                                    _ => {
-                                        return Err(anyhow::Error::msg(
-                                            "invalid count of property",
-                                        ));
-                                    }
-                                }
-                            }
-                            result.push(MeteorologicalInfoListItem::WeatherForecast(forecasts));
-                        }
-                        "降水確率" => {
-                            let mut forecasts = Vec::new();
-                            for Item { kind, area } in items {
-                                let [property]: [_; 1] = kind
-                                    .into_iter()
-                                    .flat_map(|kind| kind.property)
-                                    .collect::<Vec<_>>()
-                                    .try_into()
-                                    .map_err(|_| anyhow::Error::msg("invalid count of property"))?;
-                                if let Property {
-                                    _type,
-                                    probability_of_precipitation_part:
-                                        Some(ProbabilityOfPrecipitationPart { mut values }),
-                                    ..
-                                } = property
-                                {
-                                    values.sort_by_key(|p| p.ref_id);
-                                    let forecast = time_defines
-                                        .iter()
-                                        .zip(values)
-                                        .map(|(time, probability)| Precipitation {
-                                            time: time.clone(),
-                                            probability,
-                                        })
-                                        .collect();
-                                    forecasts.push(AreaForecast { area, forecast });
-                                } else {
-                                    return Err(anyhow::Error::msg(
-                                        "Property has no some property",
-                                    ));
-                                }
-                            }
-                            result.push(MeteorologicalInfoListItem::ProbabilityOfPrecipitation(
-                                forecasts,
-                            ));
-                        }
-                        "３時間内卓越天気" | "３時間内代表風" => {
-                            let mut forecasts = Vec::new();
-                            for Item { kind, area } in items {
-                                let mut properties: [_; 2] = kind
-                                    .into_iter()
-                                    .flat_map(|kind| kind.property)
-                                    .collect::<Vec<_>>()
-                                    .try_into()
-                                    .map_err(|_| anyhow::Error::msg("invalid count of property"))?;
-                                properties.sort_by_key(|p| p._type.clone());
-                                if let [Property {
-                                    _type: wind_type,
-                                    wind_direction_part:
-                                        Some(WindDirectionPart {
-                                            values: mut wind_directions,
-                                        }),
-                                    wind_speed_part:
-                                        Some(WindSpeedPart {
-                                            values: mut wind_speeds,
-                                        }),
-                                    ..
-                                }, Property {
-                                    _type: weather_type,
-                                    weather_part: Some(WeatherPart { mut weather }),
-                                    ..
-                                }] = properties
-                                {
-                                    assert_eq!(wind_type, "３時間内代表風");
-                                    assert_eq!(weather_type, "３時間内卓越天気");
-                                    wind_directions.sort_by_key(|w| w.ref_id);
-                                    wind_speeds.sort_by_key(|w| w.ref_id);
-                                    weather.sort_by_key(|w| w.ref_id);
-                                    let forecast = time_defines
-                                        .iter()
-                                        .zip(wind_directions)
-                                        .zip(wind_speeds)
-                                        .zip(weather)
-                                        .map(|(((time, wind_direction), wind_speed), weather)| {
-                                            WeatherAndWindForecast {
-                                                time: time.clone(),
-                                                weather,
-                                                wind_direction,
-                                                wind_speed,
-                                            }
-                                        })
-                                        .collect();
-                                    forecasts.push(AreaForecast { area, forecast });
-                                } else {
-                                    return Err(anyhow::Error::msg(
-                                        "Property has no some property",
-                                    ));
-                                }
-                            }
-                            result.push(MeteorologicalInfoListItem::WeatherAndWindTimeSeries(
-                                forecasts,
-                            ));
-                        }
-                        _ => {}
-                    }
+            let first_type = items
+                .first()
+                .and_then(|item| item.kind.first())
+                .and_then(|kind| kind.property.first())
+                .map(|property| property._type.clone())
+                .ok_or(ParseError::MissingElement {
+                    path: PROPERTY_PATH.into(),
+                })?;
+            let time_defines = time_defines.as_slice();
+            match (_type.as_str(), first_type.as_str()) {
+                ("区域予報", "天気" | "波" | "風") => {
+                    let forecasts = items
+                        .into_iter()
+                        .map(|item| weather_forecast(item, time_defines))
+                        .collect::<Result<_, _>>()?;
+                    result.push(MeteorologicalInfoListItem::WeatherForecast(forecasts));
                 }
-                "地点予報" => match items[0].kind[0].property[0]._type.as_str() {
-                    "３時間毎気温" => {
-                        let mut forecasts = Vec::new();
-                        for Item { kind, area } in items {
-                            let [property]: [_; 1] = kind
-                                .into_iter()
-                                .flat_map(|kind| kind.property)
-                                .collect::<Vec<_>>()
-                                .try_into()
-                                .map_err(|_| anyhow::Error::msg("invalid count of property"))?;
-                            if let Property {
-                                _type,
-                                temperature_part: Some(TemperaturePart { mut values }),
-                                ..
-                            } = property
-                            {
-                                values.sort_by_key(|p| p.ref_id);
-                                let forecast = time_defines
-                                    .iter()
-                                    .zip(values)
-                                    .map(|(time, temperature)| TemperatureTimeSeries {
-                                        time: time.clone(),
-                                        temperature,
-                                    })
-                                    .collect();
-                                forecasts.push(AreaForecast { area, forecast });
-                            } else {
-                                return Err(anyhow::Error::msg("Property has no some property"));
-                            }
-                        }
-                        result.push(MeteorologicalInfoListItem::TemperatureTimeSeries(forecasts));
-                    }
-                    _ => {
-                        let mut forecasts = Vec::new();
-                        for Item { kind, area } in items {
-                            if kind.iter().flat_map(|kind| &kind.property).any(
-                                |Property {
-                                     temperature_part, ..
-                                 }| {
-                                    temperature_part.is_none()
-                                        || temperature_part.as_ref().unwrap().values.is_empty()
-                                },
-                            ) {
-                                return Err(anyhow::Error::msg("Property has no some property"));
-                            }
-                            let forecast = time_defines
-                                .iter()
-                                .zip(kind.into_iter().flat_map(|kind| kind.property))
-                                .map(
-                                    |(
-                                        time,
-                                        Property {
-                                            _type,
-                                            temperature_part,
-                                            ..
-                                        },
-                                    )| TemperatureForecast {
-                                        time: time.clone(),
-                                        _type,
-                                        temperature: temperature_part.unwrap().values.remove(0),
-                                    },
-                                )
-                                .collect();
-                            forecasts.push(AreaForecast { area, forecast });
-                        }
-                        result.push(MeteorologicalInfoListItem::TemperatureForecast(forecasts));
-                    }
-                },
-                s => {
-                    return Err(anyhow::Error::msg(format!(
-                        "unknown TimeSeriesInfo::_type {}",
-                        s
-                    )));
+                ("区域予報", "降水確率") => {
+                    let forecasts = items
+                        .into_iter()
+                        .map(|item| probability_of_precipitation(item, time_defines))
+                        .collect::<Result<_, _>>()?;
+                    result.push(MeteorologicalInfoListItem::ProbabilityOfPrecipitation(
+                        forecasts,
+                    ));
+                }
+                ("区域予報", "３時間内卓越天気" | "３時間内代表風") => {
+                    let forecasts = items
+                        .into_iter()
+                        .map(|item| weather_and_wind_time_series(item, time_defines))
+                        .collect::<Result<_, _>>()?;
+                    result.push(MeteorologicalInfoListItem::WeatherAndWindTimeSeries(
+                        forecasts,
+                    ));
+                }
+                ("区域予報", _) => {}
+                (_, "３時間毎気温") => {
+                    let forecasts = items
+                        .into_iter()
+                        .map(|item| temperature_time_series(item, time_defines))
+                        .collect::<Result<_, _>>()?;
+                    result.push(MeteorologicalInfoListItem::TemperatureTimeSeries(forecasts));
+                }
+                _ => {
+                    let forecasts = items
+                        .into_iter()
+                        .map(|item| temperature_forecast(item, time_defines))
+                        .collect::<Result<_, _>>()?;
+                    result.push(MeteorologicalInfoListItem::TemperatureForecast(forecasts));
                 }
             }
         }
@@ -495,6 +340,353 @@ impl MeteorologicalInfoListItem {
     }
 }
 
+fn weather_forecast(
+    Item { kind, area, .. }: Item,
+    time_defines: &[TimeDefine],
+) -> Result<AreaForecast<WeatherForecast>, ParseError> {
+    let mut properties = into_properties(kind);
+    if !matches!(properties.len(), 2 | 3) {
+        return Err(ParseError::UnexpectedPropertyCount {
+            path: PROPERTY_PATH.into(),
+            found: properties.len(),
+        });
+    }
+    let weather = take_property(&mut properties, "天気")?;
+    let wind = take_property(&mut properties, "風")?;
+    let wave_height = if properties.is_empty() {
+        None
+    } else {
+        Some(take_property(&mut properties, "波")?)
+    };
+
+    let weather_forecast_part = align_to_time_defines(
+        weather
+            .detail_forecast
+            .ok_or(ParseError::MissingElement {
+                path: DETAIL_FORECAST_PATH.into(),
+            })?
+            .weather_forecast_part,
+        time_defines,
+        |w| Some(w.ref_id),
+        "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/DetailForecast/WeatherForecastPart",
+    )?;
+    let weather_values = align_to_time_defines(
+        weather
+            .weather_part
+            .ok_or(ParseError::MissingElement {
+                path: WEATHER_PART_PATH.into(),
+            })?
+            .weather,
+        time_defines,
+        |w| w.ref_id,
+        "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/WeatherPart/Weather",
+    )?;
+    let weather_code = align_to_time_defines(
+        weather
+            .weather_code_part
+            .ok_or(ParseError::MissingElement {
+                path: WEATHER_CODE_PART_PATH.into(),
+            })?
+            .weather_code,
+        time_defines,
+        |w| Some(w.ref_id),
+        "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/WeatherCodePart/WeatherCode",
+    )?;
+    let wind_forecast_part = align_to_time_defines(
+        wind.detail_forecast
+            .ok_or(ParseError::MissingElement {
+                path: DETAIL_FORECAST_PATH.into(),
+            })?
+            .wind_forecast_part,
+        time_defines,
+        |w| Some(w.ref_id),
+        "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/DetailForecast/WindForecastPart",
+    )?;
+    let wave_height_forecast_part = match wave_height {
+        Some(wave_height) => align_to_time_defines(
+            wave_height
+                .detail_forecast
+                .ok_or(ParseError::MissingElement {
+                    path: DETAIL_FORECAST_PATH.into(),
+                })?
+                .wave_height_forecast_part,
+            time_defines,
+            |w| Some(w.ref_id),
+            "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/DetailForecast/WaveHeightForecastPart",
+        )?
+        .into_iter()
+        .map(Some)
+        .collect(),
+        None => vec![None; time_defines.len()],
+    };
+    let forecast = time_defines
+        .iter()
+        .zip(weather_forecast_part)
+        .zip(weather_values)
+        .zip(weather_code)
+        .zip(wind_forecast_part)
+        .zip(wave_height_forecast_part)
+        .map(
+            |(((((time, weather_text), weather), weather_code), wind), wave_height)| {
+                WeatherForecast {
+                    time: time.clone(),
+                    weather_text,
+                    weather,
+                    weather_code,
+                    wind,
+                    wave_height,
+                }
+            },
+        )
+        .collect();
+    let area = area.ok_or(ParseError::MissingElement {
+        path: AREA_PATH.into(),
+    })?;
+    Ok(AreaForecast { area, forecast })
+}
+
+fn probability_of_precipitation(
+    Item { kind, area, .. }: Item,
+    time_defines: &[TimeDefine],
+) -> Result<AreaForecast<Precipitation>, ParseError> {
+    let [property] = into_property_array::<1>(kind)?;
+    let values = align_to_time_defines(
+        property
+            .probability_of_precipitation_part
+            .ok_or(ParseError::MissingElement {
+                path: "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/ProbabilityOfPrecipitationPart".into(),
+            })?
+            .values,
+        time_defines,
+        |p| Some(p.ref_id),
+        "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/ProbabilityOfPrecipitationPart/ProbabilityOfPrecipitation",
+    )?;
+    let forecast = time_defines
+        .iter()
+        .zip(values)
+        .map(|(time, probability)| Precipitation {
+            time: time.clone(),
+            probability,
+        })
+        .collect();
+    let area = area.ok_or(ParseError::MissingElement {
+        path: AREA_PATH.into(),
+    })?;
+    Ok(AreaForecast { area, forecast })
+}
+
+fn weather_and_wind_time_series(
+    Item { kind, area, .. }: Item,
+    time_defines: &[TimeDefine],
+) -> Result<AreaForecast<WeatherAndWindForecast>, ParseError> {
+    let mut properties = Vec::from(into_property_array::<2>(kind)?);
+    let wind = take_property(&mut properties, "３時間内代表風")?;
+    let weather = take_property(&mut properties, "３時間内卓越天気")?;
+    let wind_directions = align_to_time_defines(
+        wind.wind_direction_part
+            .ok_or(ParseError::MissingElement {
+                path: "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/WindDirectionPart"
+                    .into(),
+            })?
+            .values,
+        time_defines,
+        |w| Some(w.ref_id),
+        "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/WindDirectionPart/WindDirection",
+    )?;
+    let wind_speeds = align_to_time_defines(
+        wind.wind_speed_part
+            .ok_or(ParseError::MissingElement {
+                path: "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/WindSpeedPart"
+                    .into(),
+            })?
+            .values,
+        time_defines,
+        |w| Some(w.ref_id),
+        "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/WindSpeedPart/WindSpeedLevel",
+    )?;
+    let weather = align_to_time_defines(
+        weather
+            .weather_part
+            .ok_or(ParseError::MissingElement {
+                path: WEATHER_PART_PATH.into(),
+            })?
+            .weather,
+        time_defines,
+        |w| w.ref_id,
+        "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/WeatherPart/Weather",
+    )?;
+    let forecast = time_defines
+        .iter()
+        .zip(wind_directions)
+        .zip(wind_speeds)
+        .zip(weather)
+        .map(
+            |(((time, wind_direction), wind_speed), weather)| WeatherAndWindForecast {
+                time: time.clone(),
+                weather,
+                wind_direction,
+                wind_speed,
+            },
+        )
+        .collect();
+    let area = area.ok_or(ParseError::MissingElement {
+        path: AREA_PATH.into(),
+    })?;
+    Ok(AreaForecast { area, forecast })
+}
+
+fn temperature_time_series(
+    Item { kind, station, .. }: Item,
+    time_defines: &[TimeDefine],
+) -> Result<StationForecast<TemperatureTimeSeries>, ParseError> {
+    let [property] = into_property_array::<1>(kind)?;
+    let values = align_to_time_defines(
+        property
+            .temperature_part
+            .ok_or(ParseError::MissingElement {
+                path: TEMPERATURE_PART_PATH.into(),
+            })?
+            .values,
+        time_defines,
+        |t| Some(t.ref_id),
+        "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/TemperaturePart/Temperature",
+    )?;
+    let forecast = time_defines
+        .iter()
+        .zip(values)
+        .map(|(time, temperature)| TemperatureTimeSeries {
+            time: time.clone(),
+            temperature,
+        })
+        .collect();
+    let station = station.ok_or(ParseError::MissingElement {
+        path: STATION_PATH.into(),
+    })?;
+    Ok(StationForecast { station, forecast })
+}
+
+/// 時系列の値をrefIDで`time_defines`の順に並べる
+///
+/// 値の個数が`time_defines`と異なるか,refIDが`time_defines`に無いか重複している場合はエラー
+fn align_to_time_defines<T>(
+    values: Vec<T>,
+    time_defines: &[TimeDefine],
+    ref_id: impl Fn(&T) -> Option<usize>,
+    path: &'static str,
+) -> Result<Vec<T>, ParseError> {
+    if values.len() != time_defines.len() {
+        return Err(ParseError::UnexpectedValueCount {
+            path: path.into(),
+            expected: time_defines.len(),
+            found: values.len(),
+        });
+    }
+    let mut slots: Vec<Option<T>> = time_defines.iter().map(|_| None).collect();
+    for value in values {
+        let id = ref_id(&value).ok_or_else(|| ParseError::MissingElement {
+            path: format!("{}@refID", path).into(),
+        })?;
+        let slot = time_defines
+            .iter()
+            .position(|t| t.time_id == id)
+            .map(|i| &mut slots[i])
+            .filter(|slot| slot.is_none())
+            .ok_or_else(|| ParseError::InvalidValue {
+                path: format!("{}@refID", path).into(),
+                value: id.to_string(),
+            })?;
+        *slot = Some(value);
+    }
+    Ok(slots.into_iter().flatten().collect())
+}
+
+fn temperature_forecast(
+    Item { kind, station, .. }: Item,
+    time_defines: &[TimeDefine],
+) -> Result<StationForecast<TemperatureForecast>, ParseError> {
+    let properties = into_properties(kind);
+    if properties.len() != time_defines.len() {
+        return Err(ParseError::UnexpectedPropertyCount {
+            path: PROPERTY_PATH.into(),
+            found: properties.len(),
+        });
+    }
+    let forecast = properties
+        .into_iter()
+        .map(
+            |Property {
+                 _type,
+                 temperature_part,
+                 ..
+             }| {
+                let kind = TemperatureKind::from_japanese(&_type).ok_or(
+                    ParseError::InvalidValue {
+                        path: "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/Type".into(),
+                        value: _type,
+                    },
+                )?;
+                let temperature = temperature_part
+                    .and_then(|part| part.values.into_iter().next())
+                    .ok_or(ParseError::MissingElement {
+                        path: TEMPERATURE_PART_PATH.into(),
+                    })?;
+                let time = time_defines
+                    .iter()
+                    .find(|t| t.time_id == temperature.ref_id)
+                    .ok_or_else(|| ParseError::InvalidValue {
+                        path: "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/TemperaturePart/Temperature@refID".into(),
+                        value: temperature.ref_id.to_string(),
+                    })?;
+                Ok(TemperatureForecast {
+                    time: time.clone(),
+                    kind,
+                    temperature,
+                })
+            },
+        )
+        .collect::<Result<_, ParseError>>()?;
+    let station = station.ok_or(ParseError::MissingElement {
+        path: STATION_PATH.into(),
+    })?;
+    Ok(StationForecast { station, forecast })
+}
+
+fn into_properties(kind: Vec<Kind>) -> Vec<Property> {
+    kind.into_iter().flat_map(|kind| kind.property).collect()
+}
+
+fn into_property_array<const N: usize>(kind: Vec<Kind>) -> Result<[Property; N], ParseError> {
+    into_properties(kind)
+        .try_into()
+        .map_err(|p: Vec<_>| ParseError::UnexpectedPropertyCount {
+            path: PROPERTY_PATH.into(),
+            found: p.len(),
+        })
+}
+
+fn take_property(
+    properties: &mut Vec<Property>,
+    property_type: &'static str,
+) -> Result<Property, ParseError> {
+    let index = properties
+        .iter()
+        .position(|p| p._type == property_type)
+        .ok_or(ParseError::MissingProperty { property_type })?;
+    Ok(properties.remove(index))
+}
+
+const AREA_PATH: &str = "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Area";
+const STATION_PATH: &str = "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Station";
+const PROPERTY_PATH: &str = "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property";
+const DETAIL_FORECAST_PATH: &str =
+    "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/DetailForecast";
+const WEATHER_PART_PATH: &str =
+    "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/WeatherPart";
+const WEATHER_CODE_PART_PATH: &str =
+    "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/WeatherCodePart";
+const TEMPERATURE_PART_PATH: &str =
+    "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/TemperaturePart";
+
 #[derive(Debug, Clone)]
 struct AreaForecast<T> {
     area: Area,
@@ -502,6 +694,13 @@ struct AreaForecast<T> {
 }
 
 #[derive(Debug, Clone)]
+struct StationForecast<T> {
+    station: Station,
+    forecast: Vec<T>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct WeatherForecast {
     pub time: TimeDefine,
     pub weather_text: WeatherForecastPart,
@@ -512,12 +711,14 @@ pub struct WeatherForecast {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Precipitation {
     pub time: TimeDefine,
     pub probability: ProbabilityOfPrecipitation,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct WeatherAndWindForecast {
     pub time: TimeDefine,
     pub weather: Weather,
@@ -526,13 +727,87 @@ pub struct WeatherAndWindForecast {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct TemperatureForecast {
     pub time: TimeDefine,
-    pub _type: String,
+    pub kind: TemperatureKind,
     pub temperature: Temperature,
 }
 
+/// 気温予報の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum TemperatureKind {
+    /// 日中の最高気温
+    DaytimeMax,
+    /// 最高気温
+    Max,
+    /// 朝の最低気温
+    MorningMin,
+    /// 最低気温
+    Min,
+}
+
+impl TemperatureKind {
+    pub fn from_japanese(s: &str) -> Option<Self> {
+        match s {
+            "日中の最高気温" => Some(TemperatureKind::DaytimeMax),
+            "最高気温" => Some(TemperatureKind::Max),
+            "朝の最低気温" => Some(TemperatureKind::MorningMin),
+            "最低気温" => Some(TemperatureKind::Min),
+            _ => None,
+        }
+    }
+
+    pub fn is_max(self) -> bool {
+        matches!(self, TemperatureKind::DaytimeMax | TemperatureKind::Max)
+    }
+
+    pub fn is_min(self) -> bool {
+        !self.is_max()
+    }
+}
+
+/// 1日分の最低,最高気温
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct DailyTemperatureForecast {
+    /// 電文の時刻(日本時間)での日付
+    pub date: NaiveDate,
+    pub min: Option<i32>,
+    pub max: Option<i32>,
+}
+
+impl StationInfoList {
+    /// 気温予報を日付毎にまとめたもの,日付順
+    pub fn daily_temperatures(&self) -> Vec<DailyTemperatureForecast> {
+        let mut days: Vec<DailyTemperatureForecast> = Vec::new();
+        for forecast in self.temperature_forecast.iter().flatten() {
+            let date = forecast.time.datetime.naive_local().date();
+            let i = match days.iter().position(|d| d.date == date) {
+                Some(i) => i,
+                None => {
+                    days.push(DailyTemperatureForecast {
+                        date,
+                        min: None,
+                        max: None,
+                    });
+                    days.len() - 1
+                }
+            };
+            if forecast.kind.is_max() {
+                days[i].max = Some(forecast.temperature.value);
+            } else {
+                days[i].min = Some(forecast.temperature.value);
+            }
+        }
+        days.sort_by_key(|d| d.date);
+        days
+    }
+}
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct TemperatureTimeSeries {
     pub time: TimeDefine,
     pub temperature: Temperature,
@@ -546,14 +821,17 @@ struct ReportTmp {
     head: Head,
     #[serde(alias = "Body")]
     body: BodyTmp,
+    #[serde(alias = "jmx_add:Addition")]
+    addition: Option<Addition>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct Control {
     #[serde(alias = "Title")]
     pub title: String,
     #[serde(alias = "DateTime")]
-    pub datetime: DateTime<Local>,
+    pub datetime: DateTime<FixedOffset>,
     #[serde(alias = "Status")]
     pub status: ControlStatus,
     #[serde(alias = "EditorialOffice")]
@@ -563,6 +841,7 @@ pub struct Control {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub enum ControlStatus {
     #[serde(alias = "通常")]
     Normal,
@@ -573,24 +852,42 @@ pub enum ControlStatus {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct Head {
     #[serde(alias = "Title")]
     pub title: String,
     #[serde(alias = "ReportDateTime")]
-    pub report_datetime: DateTime<Local>,
+    pub report_datetime: DateTime<FixedOffset>,
     #[serde(alias = "TargetDateTime")]
-    pub target_datetime: DateTime<Local>,
+    pub target_datetime: DateTime<FixedOffset>,
     #[serde(alias = "TargetDuration")]
-    pub target_duration: String,
+    pub target_duration: Option<String>,
+    /// 地震,津波等の事象を識別する番号
+    #[serde(alias = "EventID")]
+    pub event_id: Option<String>,
     #[serde(alias = "InfoType")]
     pub info_type: InfoType,
+    /// 同一事象に対する情報の番号
+    #[serde(alias = "Serial")]
+    pub serial: Option<String>,
     #[serde(alias = "InfoKind")]
     pub info_kind: String,
     #[serde(alias = "InfoKindVersion")]
     pub info_kind_version: String,
 }
 
+/// 付加事項(jmx_add:Addition)
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct Addition {
+    /// 運用の変更等のお知らせ
+    #[serde(alias = "jmx_add:Notice")]
+    #[serde(default)]
+    pub notices: Vec<String>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub enum InfoType {
     #[serde(alias = "発表")]
     Announcement,
@@ -598,6 +895,8 @@ pub enum InfoType {
     Correction,
     #[serde(alias = "遅延")]
     Delay,
+    #[serde(alias = "取消")]
+    Cancellation,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -619,9 +918,11 @@ struct MeteorologicalInfosTmp {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct MeteorologicalInfo {
     #[serde(alias = "DateTime")]
-    pub date_time: DateTime<Local>,
+    #[serde(rename(serialize = "datetime"), alias = "datetime")]
+    pub date_time: DateTime<FixedOffset>,
     #[serde(alias = "Duration")]
     pub duration: String,
     #[serde(alias = "Item")]
@@ -629,6 +930,7 @@ pub struct MeteorologicalInfo {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct MeteorologicalInfoItem {
     #[serde(alias = "Kind")]
     pub kind: MeteorologicalInfoKind,
@@ -637,14 +939,17 @@ pub struct MeteorologicalInfoItem {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct MeteorologicalInfoKind {
     #[serde(alias = "Property")]
     pub property: MeteorologicalInfoProperty,
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct MeteorologicalInfoProperty {
     #[serde(alias = "Type")]
+    #[serde(rename(serialize = "type"), alias = "type")]
     pub _type: String,
     #[serde(alias = "Text")]
     pub text: String,
@@ -665,11 +970,12 @@ struct TimeDefines {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct TimeDefine {
     #[serde(alias = "timeId")]
     pub time_id: usize,
     #[serde(alias = "DateTime")]
-    pub datetime: DateTime<Local>,
+    pub datetime: DateTime<FixedOffset>,
     #[serde(alias = "Duration")]
     pub duration: Option<String>,
     #[serde(alias = "Name")]
@@ -681,8 +987,9 @@ struct Item {
     #[serde(alias = "Kind")]
     kind: Vec<Kind>,
     #[serde(alias = "Area")]
+    area: Option<Area>,
     #[serde(alias = "Station")]
-    area: Area,
+    station: Option<Station>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -725,6 +1032,7 @@ struct DetailForecast {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct WeatherForecastPart {
     #[serde(alias = "refID")]
     pub ref_id: usize,
@@ -738,30 +1046,35 @@ pub struct WeatherForecastPart {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct WeatherForecastBase {
-    #[serde(alias = "Weather")]
+    #[serde(alias = "jmx_eb:Weather")]
     pub weather: Weather,
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct Temporary {
     #[serde(alias = "TimeModifier")]
     pub time_modifier: String,
-    #[serde(alias = "Weather")]
+    #[serde(alias = "jmx_eb:Weather")]
     pub weather: Weather,
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct Weather {
     #[serde(alias = "refID")]
     pub ref_id: Option<usize>,
     #[serde(alias = "type")]
+    #[serde(rename(serialize = "type"))]
     pub _type: String,
     #[serde(alias = "$value")]
     pub value: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct WindForecastPart {
     #[serde(alias = "refID")]
     pub ref_id: usize,
@@ -770,6 +1083,7 @@ pub struct WindForecastPart {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct WaveHeightForecastPart {
     #[serde(alias = "refID")]
     pub ref_id: usize,
@@ -779,21 +1093,23 @@ pub struct WaveHeightForecastPart {
 
 #[derive(Debug, Clone, Deserialize)]
 struct WeatherPart {
-    #[serde(alias = "Weather")]
+    #[serde(alias = "jmx_eb:Weather")]
     weather: Vec<Weather>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 struct WeatherCodePart {
-    #[serde(alias = "WeatherCode")]
+    #[serde(alias = "jmx_eb:WeatherCode")]
     weather_code: Vec<WeatherCode>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct WeatherCode {
     #[serde(alias = "refID")]
     pub ref_id: usize,
     #[serde(alias = "type")]
+    #[serde(rename(serialize = "type"))]
     pub _type: String,
     #[serde(alias = "$value")]
     pub value: usize,
@@ -801,17 +1117,19 @@ pub struct WeatherCode {
 
 #[derive(Debug, Clone, Deserialize)]
 struct ProbabilityOfPrecipitationPart {
-    #[serde(alias = "ProbabilityOfPrecipitation")]
+    #[serde(alias = "jmx_eb:ProbabilityOfPrecipitation")]
     values: Vec<ProbabilityOfPrecipitation>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct ProbabilityOfPrecipitation {
     pub condition: String,
     pub description: String,
     #[serde(alias = "refID")]
     pub ref_id: usize,
     #[serde(alias = "type")]
+    #[serde(rename(serialize = "type"))]
     pub _type: String,
     pub unit: String,
     #[serde(alias = "$value")]
@@ -820,16 +1138,18 @@ pub struct ProbabilityOfPrecipitation {
 
 #[derive(Debug, Clone, Deserialize)]
 struct TemperaturePart {
-    #[serde(alias = "Temperature")]
+    #[serde(alias = "jmx_eb:Temperature")]
     values: Vec<Temperature>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct Temperature {
     pub description: String,
     #[serde(alias = "refID")]
     pub ref_id: usize,
     #[serde(alias = "type")]
+    #[serde(rename(serialize = "type"))]
     pub _type: String,
     pub unit: String,
     #[serde(alias = "$value")]
@@ -838,15 +1158,17 @@ pub struct Temperature {
 
 #[derive(Debug, Clone, Deserialize)]
 struct WindDirectionPart {
-    #[serde(alias = "WindDirection")]
+    #[serde(alias = "jmx_eb:WindDirection")]
     values: Vec<WindDirection>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct WindDirection {
     #[serde(alias = "refID")]
     pub ref_id: usize,
     #[serde(alias = "type")]
+    #[serde(rename(serialize = "type"))]
     pub _type: String,
     pub unit: String,
     #[serde(alias = "$value")]
@@ -855,26 +1177,409 @@ pub struct WindDirection {
 
 #[derive(Debug, Clone, Deserialize)]
 struct WindSpeedPart {
-    #[serde(alias = "WindSpeedLevel")]
+    #[serde(alias = "jmx_eb:WindSpeedLevel")]
     values: Vec<WindSpeedLevel>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct WindSpeedLevel {
     pub description: String,
     pub range: String,
     #[serde(alias = "refID")]
     pub ref_id: usize,
     #[serde(alias = "type")]
+    #[serde(rename(serialize = "type"))]
     pub _type: String,
     #[serde(alias = "$value")]
     pub value: u32,
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
 pub struct Area {
     #[serde(alias = "Name")]
     pub name: String,
     #[serde(alias = "Code")]
     pub code: usize,
 }
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct Station {
+    #[serde(alias = "Name")]
+    pub name: String,
+    #[serde(alias = "Code")]
+    pub code: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    use super::*;
+
+    const R1: &str = include_str!("../benches/data/r1.xml");
+
+    /// 再現できるように固定のシードから生成する乱数
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn below(&mut self, n: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 % n as u64) as usize
+        }
+    }
+
+    const VALUES: &[&str] = &[
+        "",
+        "0",
+        "-1",
+        "18446744073709551616",
+        "晴れ",
+        "天気",
+        "３時間内代表風",
+        "最高気温",
+        "2021-13-40T00:00:00+09:00",
+        "&amp;",
+    ];
+
+    /// 要素の文字列か属性の値を置き換える
+    fn replace_value(line: &str, rng: &mut XorShift) -> String {
+        let value = VALUES[rng.below(VALUES.len())];
+        let quotes = line.match_indices('"').map(|(i, _)| i).collect::<Vec<_>>();
+        if quotes.len() >= 2 && rng.below(2) == 0 {
+            let i = rng.below(quotes.len() / 2) * 2;
+            return format!("{}{}{}", &line[..=quotes[i]], value, &line[quotes[i + 1]..]);
+        }
+        match (line.find('>'), line.rfind('<')) {
+            (Some(start), Some(end)) if start < end => {
+                format!("{}{}{}", &line[..=start], value, &line[end..])
+            }
+            _ => line.to_owned(),
+        }
+    }
+
+    /// 1行で完結する要素か
+    fn is_element(line: &str) -> bool {
+        let line = line.trim();
+        line.starts_with('<')
+            && !line.starts_with("</")
+            && (line.ends_with("/>") || line.contains("</"))
+    }
+
+    /// 大半はXMLとして正しいままにして変換処理まで届くようにする
+    fn mutate(lines: &mut Vec<String>, rng: &mut XorShift) {
+        let i = rng.below(lines.len());
+        let j = rng.below(lines.len());
+        match rng.below(20) {
+            0..=3 if is_element(&lines[i]) && lines.len() > 1 => {
+                lines.remove(i);
+            }
+            4..=7 if is_element(&lines[i]) => {
+                let line = lines[i].clone();
+                lines.insert(i, line);
+            }
+            8..=9 if is_element(&lines[i]) && is_element(&lines[j]) => lines.swap(i, j),
+            19 => lines.truncate(i.max(1)),
+            _ => lines[i] = replace_value(&lines[i], rng),
+        }
+    }
+
+    #[test]
+    fn mutated_document_does_not_panic() {
+        let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+        for _ in 0..3000 {
+            let mut lines = R1.lines().map(str::to_owned).collect::<Vec<_>>();
+            for _ in 0..=rng.below(3) {
+                mutate(&mut lines, &mut rng);
+            }
+            let xml = lines.join("\n");
+            if catch_unwind(AssertUnwindSafe(|| xml.parse::<Report>())).is_err() {
+                panic!("panicked while parsing\n{}", xml);
+            }
+        }
+    }
+
+    #[test]
+    fn station_area_from_registry() {
+        let report: Report = R1.parse().unwrap();
+        assert_eq!(report.areas.len(), 2);
+        assert_eq!(report.stations.len(), 1);
+        assert_eq!(report.station(44132).unwrap().area_code, Some(130010));
+        assert_eq!(report.area_of_station(44132).unwrap().area.code, 130010);
+        let codes = |area| {
+            report
+                .stations_in_area(area)
+                .map(|s| s.station.code)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(codes(130010), [44132]);
+        assert!(codes(130020).is_empty());
+
+        let report: Report = R1
+            .replace("<Code>44132</Code>", "<Code>99999</Code>")
+            .parse()
+            .unwrap();
+        assert_eq!(report.station(99999).unwrap().area_code, None);
+
+        // 東京以外の地点
+        let report: Report = R1
+            .replace("<Code>130010</Code>", "<Code>270000</Code>")
+            .replace("<Code>44132</Code>", "<Code>62078</Code>")
+            .parse()
+            .unwrap();
+        assert_eq!(report.station(62078).unwrap().area_code, Some(270000));
+        assert_eq!(report.area_of_station(62078).unwrap().area.code, 270000);
+    }
+
+    #[test]
+    fn station_area_from_document_order() {
+        // 伊豆諸島北部に対応する地点をコード表にないコードで加える
+        let first = R1.find("<Station>").unwrap();
+        let item = R1[..first].rfind("<Item>").unwrap();
+        let end = first + R1[first..].find("</Item>").unwrap() + "</Item>".len();
+        let station = R1[item..end].replace(
+            "<Station><Name>東京</Name><Code>44132</Code></Station>",
+            "<Station><Name>大島</Name><Code>99999</Code></Station>",
+        );
+        let xml = format!("{}\n{}{}", &R1[..end], station, &R1[end..]);
+        let report: Report = xml.parse().unwrap();
+        assert_eq!(report.stations.len(), 2);
+        assert_eq!(report.station(44132).unwrap().area_code, Some(130010));
+        assert_eq!(report.station(99999).unwrap().area_code, Some(130020));
+        assert_eq!(report.area_of_station(99999).unwrap().area.code, 130020);
+    }
+
+    /// 独自予報を除いて電文中に最初に現れた順の区域か地点のコード
+    fn codes_in_document(xml: &str, element: &str) -> Vec<usize> {
+        let xml = xml
+            .split("<MeteorologicalInfos type=\"独自予報\">")
+            .next()
+            .unwrap();
+        let mut codes = Vec::new();
+        for part in xml.split(&format!("<{}><Name>", element)).skip(1) {
+            let start = part.find("<Code>").unwrap() + "<Code>".len();
+            let end = part.find("</Code>").unwrap();
+            let code = part[start..end].parse().unwrap();
+            if !codes.contains(&code) {
+                codes.push(code);
+            }
+        }
+        codes
+    }
+
+    #[test]
+    fn body_keeps_document_order() {
+        let report: Report = R1.parse().unwrap();
+        let areas = report.areas.iter().map(|a| a.area.code).collect::<Vec<_>>();
+        assert_eq!(areas, codes_in_document(R1, "Area"));
+        let stations = report
+            .stations
+            .iter()
+            .map(|s| s.station.code)
+            .collect::<Vec<_>>();
+        assert_eq!(stations, codes_in_document(R1, "Station"));
+
+        // コード順ではなく電文中の順
+        let swapped = R1
+            .replace("<Code>130010</Code>", "<Code>tmp</Code>")
+            .replace("<Code>130020</Code>", "<Code>130010</Code>")
+            .replace("<Code>tmp</Code>", "<Code>130020</Code>");
+        let report: Report = swapped.parse().unwrap();
+        let areas = report.areas.iter().map(|a| a.area.code).collect::<Vec<_>>();
+        assert_eq!(areas, [130020, 130010]);
+        assert_eq!(areas, codes_in_document(&swapped, "Area"));
+        for _ in 0..10 {
+            let again: Report = swapped.parse().unwrap();
+            let codes = again.areas.iter().map(|a| a.area.code).collect::<Vec<_>>();
+            assert_eq!(codes, areas);
+        }
+    }
+
+    #[test]
+    fn lookup_by_code() {
+        let report: Report = R1.parse().unwrap();
+
+        let tokyo = report.area(130010).unwrap();
+        assert_eq!(tokyo.area.name, "東京地方");
+        assert_eq!(tokyo.weather_forecast.as_ref().unwrap().len(), 2);
+        let precipitation = tokyo.probability_of_precipitation.as_ref().unwrap();
+        assert_eq!(
+            precipitation
+                .iter()
+                .map(|p| p.probability.value)
+                .collect::<Vec<_>>(),
+            [50, 20]
+        );
+        assert_eq!(
+            tokyo.weather_and_wind_time_series.as_ref().unwrap().len(),
+            2
+        );
+
+        let izu = report.area(130020).unwrap();
+        assert_eq!(izu.area.name, "伊豆諸島北部");
+        let forecast = izu.weather_forecast.as_ref().unwrap();
+        assert_eq!(forecast[0].weather_code.value, 200);
+        assert!(forecast.iter().all(|f| f.wave_height.is_none()));
+        assert!(izu.probability_of_precipitation.is_none());
+        assert!(izu.weather_and_wind_time_series.is_none());
+
+        let station = report.station(44132).unwrap();
+        assert_eq!(station.station.name, "東京");
+        assert_eq!(station.temperature_forecast.as_ref().unwrap().len(), 3);
+        assert_eq!(station.temperature_time_series.as_ref().unwrap().len(), 2);
+
+        assert!(report.area(130030).is_none());
+        assert!(report.area(44132).is_none());
+        assert!(report.station(130010).is_none());
+        assert!(report.station(44131).is_none());
+        assert!(report.area_of_station(44131).is_none());
+    }
+
+    #[test]
+    fn root_addition() {
+        let report: Report = R1.parse().unwrap();
+        assert!(report.addition.is_none());
+
+        let xml = R1.replace(
+            "</Body>\n</Report>",
+            "</Body>\n<jmx_add:Addition><jmx_add:Notice>お知らせ１</jmx_add:Notice><jmx_add:Notice>お知らせ２</jmx_add:Notice></jmx_add:Addition>\n</Report>",
+        );
+        let report: Report = xml.parse().unwrap();
+        assert_eq!(
+            report.addition.unwrap().notices,
+            ["お知らせ１", "お知らせ２"]
+        );
+
+        // 接頭辞によらず名前空間で判定する
+        let xml = R1.replace("xmlns:jmx_add=", "xmlns:add=").replace(
+            "</Body>\n</Report>",
+            "</Body>\n<add:Addition><add:Notice>お知らせ</add:Notice></add:Addition>\n</Report>",
+        );
+        let report: Report = xml.parse().unwrap();
+        assert_eq!(report.addition.unwrap().notices, ["お知らせ"]);
+    }
+
+    #[test]
+    fn daily_temperatures() {
+        let report: Report = R1.parse().unwrap();
+        let date = |d| NaiveDate::from_ymd_opt(2021, 10, d).unwrap();
+        assert_eq!(
+            report.station(44132).unwrap().daily_temperatures(),
+            [
+                DailyTemperatureForecast {
+                    date: date(1),
+                    min: None,
+                    max: Some(24),
+                },
+                DailyTemperatureForecast {
+                    date: date(2),
+                    min: Some(18),
+                    max: Some(27),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn values_are_ordered_by_ref_id() {
+        let xml = R1.replace(
+            r#"<WeatherCodePart><jmx_eb:WeatherCode refID="1" type="天気">313</jmx_eb:WeatherCode><jmx_eb:WeatherCode refID="2" type="天気">101</jmx_eb:WeatherCode></WeatherCodePart>"#,
+            r#"<WeatherCodePart><jmx_eb:WeatherCode refID="2" type="天気">101</jmx_eb:WeatherCode><jmx_eb:WeatherCode refID="1" type="天気">313</jmx_eb:WeatherCode></WeatherCodePart>"#,
+        );
+        let report: Report = xml.parse().unwrap();
+        let forecast = report
+            .area(130010)
+            .unwrap()
+            .weather_forecast
+            .as_ref()
+            .unwrap();
+        let codes = forecast
+            .iter()
+            .map(|f| f.weather_code.ref_id)
+            .collect::<Vec<_>>();
+        assert_eq!(codes, [1, 2]);
+    }
+
+    #[test]
+    fn value_count_mismatch_is_an_error() {
+        let cases = [
+            (
+                r#"<jmx_eb:WeatherCode refID="2" type="天気">101</jmx_eb:WeatherCode>"#,
+                "",
+                "WeatherCodePart/WeatherCode",
+                1,
+            ),
+            (
+                r#"<WaveHeightForecastPart refID="2"><Sentence>１メートル</Sentence></WaveHeightForecastPart>"#,
+                "",
+                "DetailForecast/WaveHeightForecastPart",
+                1,
+            ),
+            (
+                r#"<jmx_eb:ProbabilityOfPrecipitation refID="2" type="６時間降水確率" unit="%" condition="" description="２０パーセント">20</jmx_eb:ProbabilityOfPrecipitation>"#,
+                r#"<jmx_eb:ProbabilityOfPrecipitation refID="2" type="６時間降水確率" unit="%" condition="" description="２０パーセント">20</jmx_eb:ProbabilityOfPrecipitation><jmx_eb:ProbabilityOfPrecipitation refID="3" type="６時間降水確率" unit="%" condition="" description="０パーセント">0</jmx_eb:ProbabilityOfPrecipitation>"#,
+                "ProbabilityOfPrecipitationPart/ProbabilityOfPrecipitation",
+                3,
+            ),
+            (
+                r#"<jmx_eb:WindSpeedLevel refID="2" type="風速階級" description="毎秒３から５メートル" range="3 6">2</jmx_eb:WindSpeedLevel>"#,
+                "",
+                "WindSpeedPart/WindSpeedLevel",
+                1,
+            ),
+            (
+                r#"<jmx_eb:Temperature refID="2" type="気温" unit="度" description="２３度">23</jmx_eb:Temperature>"#,
+                "",
+                "TemperaturePart/Temperature",
+                1,
+            ),
+        ];
+        for (from, to, part, count) in cases {
+            assert!(R1.contains(from), "{}", from);
+            let xml = R1.replacen(from, to, 1);
+            match xml.parse::<Report>() {
+                Err(ParseError::UnexpectedValueCount {
+                    path,
+                    expected,
+                    found,
+                }) => {
+                    assert_eq!(path, format!("{}/{}", PROPERTY_PATH, part));
+                    assert_eq!((expected, found), (2, count));
+                }
+                r => panic!("{}: {:?}", part, r),
+            }
+        }
+    }
+
+    #[test]
+    fn unmatched_ref_id_is_an_error() {
+        let cases = [
+            (
+                r#"<WindForecastPart refID="2"><Sentence>北の風</Sentence>"#,
+                r#"<WindForecastPart refID="3"><Sentence>北の風</Sentence>"#,
+                "DetailForecast/WindForecastPart",
+                "3",
+            ),
+            (
+                r#"<jmx_eb:Weather refID="2" type="天気">晴れ時々くもり</jmx_eb:Weather>"#,
+                r#"<jmx_eb:Weather refID="1" type="天気">晴れ時々くもり</jmx_eb:Weather>"#,
+                "WeatherPart/Weather",
+                "1",
+            ),
+        ];
+        for (from, to, part, value) in cases {
+            assert!(R1.contains(from), "{}", from);
+            match R1.replacen(from, to, 1).parse::<Report>() {
+                Err(ParseError::InvalidValue { path, value: v }) => {
+                    assert_eq!(path, format!("{}/{}@refID", PROPERTY_PATH, part));
+                    assert_eq!(v, value);
+                }
+                r => panic!("{}: {:?}", part, r),
+            }
+        }
+    }
+}
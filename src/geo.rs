@@ -0,0 +1,332 @@
+//! 緯度,経度からの区域,地点の検索
+//!
+//! 区域の境界データは呼び出し側で用意し,[`Boundaries::from_csv`](crate::geo::Boundaries::from_csv)で読み込んで渡す。
+//! 気象庁の予報区等の境界データを`kind,code,latitude longitude;...`形式のCSVに変換して使う。
+//!
+//! [`Boundaries::sample`](crate::geo::Boundaries::sample)は東京都,大阪府の一次細分区域と東京都の一部の市町村を
+//! 大まかな多角形で近似した動作確認用のデータで,他の区域の点では何も見つからず,
+//! 境界付近の点は誤った区域になりうるため,実際の検索には使わない。
+
+use std::sync::OnceLock;
+
+use crate::error::RegistryError;
+use crate::fuken_r1::{MeteorologicalInfoList, Report, StationInfoList};
+use crate::registry::{AreaEntry, AreaKind, Point, Registry};
+
+const SAMPLE_CSV: &str = include_str!("../data/boundaries.csv");
+
+/// 地球の平均半径(km)
+const EARTH_RADIUS: f64 = 6371.0;
+
+/// 区域の境界
+#[derive(Debug, Clone, PartialEq)]
+pub struct Boundary {
+    pub kind: AreaKind,
+    pub code: usize,
+    /// 外周のみの多角形,飛び地等があれば複数
+    pub polygons: Vec<Vec<Point>>,
+}
+
+impl Boundary {
+    /// 点が区域内にあるか
+    pub fn contains(&self, point: Point) -> bool {
+        self.polygons
+            .iter()
+            .any(|polygon| polygon_contains(polygon, point))
+    }
+}
+
+/// 区域の境界の一覧
+#[derive(Debug, Clone)]
+pub struct Boundaries {
+    version: String,
+    boundaries: Vec<Boundary>,
+}
+
+impl Boundaries {
+    /// 動作確認用の境界データ
+    ///
+    /// 東京都,大阪府の一部の区域を大まかに近似したもののみを含む
+    pub fn sample() -> &'static Boundaries {
+        static SAMPLE: OnceLock<Boundaries> = OnceLock::new();
+        SAMPLE.get_or_init(|| {
+            Boundaries::from_csv(SAMPLE_CSV).expect("sample boundary table is invalid")
+        })
+    }
+
+    /// `kind,code,latitude longitude;latitude longitude;...`形式のCSVから読み込む
+    ///
+    /// 同じ区域の行が複数あればそれぞれ別の多角形とする。
+    /// `#`で始まる行はコメントで,`# version: `の行があればその値をバージョンとする。
+    pub fn from_csv(csv: &str) -> Result<Boundaries, RegistryError> {
+        let mut version = String::new();
+        let mut boundaries: Vec<Boundary> = Vec::new();
+        for (i, line) in csv.lines().enumerate() {
+            let line = line.trim();
+            if let Some(comment) = line.strip_prefix('#') {
+                if let Some(v) = comment.trim().strip_prefix("version:") {
+                    version = v.trim().to_string();
+                }
+                continue;
+            }
+            if line.is_empty() {
+                continue;
+            }
+            let (kind, code, polygon) =
+                parse_line(line).map_err(|reason| RegistryError::InvalidLine {
+                    line: i + 1,
+                    reason,
+                })?;
+            match boundaries
+                .iter_mut()
+                .find(|b| b.kind == kind && b.code == code)
+            {
+                Some(boundary) => boundary.polygons.push(polygon),
+                None => boundaries.push(Boundary {
+                    kind,
+                    code,
+                    polygons: vec![polygon],
+                }),
+            }
+        }
+        Ok(Boundaries {
+            version,
+            boundaries,
+        })
+    }
+
+    /// 境界データのバージョン
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// 全ての境界
+    pub fn boundaries(&self) -> &[Boundary] {
+        &self.boundaries
+    }
+
+    /// 点を含む区域の境界
+    pub fn containing(&self, point: Point) -> impl Iterator<Item = &Boundary> {
+        self.boundaries.iter().filter(move |b| b.contains(point))
+    }
+
+    /// 点を含む指定した種別の区域の境界
+    pub fn locate(&self, kind: AreaKind, point: Point) -> Option<&Boundary> {
+        self.containing(point).find(|b| b.kind == kind)
+    }
+}
+
+fn parse_line(line: &str) -> Result<(AreaKind, usize, Vec<Point>), String> {
+    let columns: Vec<&str> = line.splitn(3, ',').map(str::trim).collect();
+    if columns.len() != 3 {
+        return Err(format!("expected 3 columns, found {}", columns.len()));
+    }
+    let kind =
+        AreaKind::from_key(columns[0]).ok_or_else(|| format!("unknown kind {}", columns[0]))?;
+    let code = columns[1]
+        .parse()
+        .map_err(|_| format!("invalid code {}", columns[1]))?;
+    let polygon = columns[2]
+        .split(';')
+        .map(|vertex| {
+            let mut degrees = vertex.split_whitespace().map(str::parse::<f64>);
+            match (degrees.next(), degrees.next(), degrees.next()) {
+                (Some(Ok(latitude)), Some(Ok(longitude)), None) => Ok(Point {
+                    latitude,
+                    longitude,
+                }),
+                _ => Err(format!("invalid vertex {}", vertex)),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if polygon.len() < 3 {
+        return Err(format!("polygon has only {} vertices", polygon.len()));
+    }
+    Ok((kind, code, polygon))
+}
+
+/// 緯度,経度を平面座標とみなした交差数判定
+fn polygon_contains(polygon: &[Point], point: Point) -> bool {
+    let mut inside = false;
+    let mut j = polygon.len() - 1;
+    for i in 0..polygon.len() {
+        let (a, b) = (polygon[i], polygon[j]);
+        if (a.latitude > point.latitude) != (b.latitude > point.latitude) {
+            let longitude = a.longitude
+                + (point.latitude - a.latitude) / (b.latitude - a.latitude)
+                    * (b.longitude - a.longitude);
+            if point.longitude < longitude {
+                inside = !inside;
+            }
+        }
+        j = i;
+    }
+    inside
+}
+
+/// 2点間の大円距離(km)
+pub fn distance(a: Point, b: Point) -> f64 {
+    let (lat1, lat2) = (a.latitude.to_radians(), b.latitude.to_radians());
+    let d_lat = lat2 - lat1;
+    let d_lon = (b.longitude - a.longitude).to_radians();
+    let h = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS * h.sqrt().asin()
+}
+
+/// 点を含む指定した種別の区域の同梱のコード表での行
+pub fn locate(boundaries: &Boundaries, kind: AreaKind, point: Point) -> Option<&'static AreaEntry> {
+    let boundary = boundaries.locate(kind, point)?;
+    Registry::bundled().get(boundary.kind, boundary.code)
+}
+
+/// [`Report::forecast_at`]の結果
+#[derive(Debug, Clone, Copy)]
+pub struct LocalForecast<'a> {
+    /// 点を含む一次細分区域の予報
+    pub area: &'a MeteorologicalInfoList,
+    /// 点に最も近い気温予報地点の予報,代表点が分かる地点が無ければNone
+    pub station: Option<&'a StationInfoList>,
+    /// 点から`station`までの距離(km)
+    pub station_distance: Option<f64>,
+}
+
+impl Report {
+    /// 緯度,経度を含む一次細分区域の予報と最寄りの気温予報地点の予報
+    ///
+    /// `boundaries`に点を含む一次細分区域が無いか,その区域の予報が電文に無ければNone。
+    /// 地点の位置は同梱のコード表のものを使う。
+    pub fn forecast_at(
+        &self,
+        boundaries: &Boundaries,
+        latitude: f64,
+        longitude: f64,
+    ) -> Option<LocalForecast<'_>> {
+        let point = Point {
+            latitude,
+            longitude,
+        };
+        let area = boundaries
+            .containing(point)
+            .filter(|b| b.kind == AreaKind::PrimarySubdivision)
+            .find_map(|b| self.area(b.code))?;
+        let registry = Registry::bundled();
+        let nearest = self
+            .stations
+            .iter()
+            .filter_map(|list| {
+                let location = registry
+                    .get(AreaKind::Station, list.station.code)?
+                    .location?;
+                Some((list, distance(point, location)))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+        Some(LocalForecast {
+            area,
+            station: nearest.map(|(list, _)| list),
+            station_distance: nearest.map(|(_, d)| d),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const R1: &str = include_str!("../benches/data/r1.xml");
+
+    fn point(latitude: f64, longitude: f64) -> Point {
+        Point {
+            latitude,
+            longitude,
+        }
+    }
+
+    fn codes(point: Point) -> Vec<(AreaKind, usize)> {
+        Boundaries::sample()
+            .containing(point)
+            .map(|b| (b.kind, b.code))
+            .collect()
+    }
+
+    #[test]
+    fn locate_sample() {
+        // 新宿
+        assert_eq!(
+            codes(point(35.69, 139.70)),
+            [(AreaKind::PrimarySubdivision, 130010)]
+        );
+        // 大阪市
+        let osaka = locate(
+            Boundaries::sample(),
+            AreaKind::PrimarySubdivision,
+            point(34.69, 135.50),
+        )
+        .unwrap();
+        assert_eq!(osaka.code, 270000);
+        assert_eq!(osaka.name, "大阪府");
+        // 八丈島
+        assert_eq!(
+            codes(point(33.11, 139.79)),
+            [
+                (AreaKind::PrimarySubdivision, 130030),
+                (AreaKind::Municipality, 1340100)
+            ]
+        );
+        // 紀伊半島沖,大阪湾
+        assert!(codes(point(33.5, 136.5)).is_empty());
+        assert!(codes(point(34.55, 135.30)).is_empty());
+        assert!(locate(
+            Boundaries::sample(),
+            AreaKind::PrimarySubdivision,
+            point(33.5, 136.5)
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn forecast_at() {
+        let boundaries = Boundaries::sample();
+        let report: Report = R1.parse().unwrap();
+        let tokyo = report.forecast_at(boundaries, 35.69, 139.70).unwrap();
+        assert_eq!(tokyo.area.area.code, 130010);
+        assert_eq!(tokyo.station.unwrap().station.code, 44132);
+        assert!(tokyo.station_distance.unwrap() < 10.0);
+        let oshima = report.forecast_at(boundaries, 34.75, 139.36).unwrap();
+        assert_eq!(oshima.area.area.code, 130020);
+        assert!(report.forecast_at(boundaries, 34.69, 135.50).is_none());
+        assert!(report.forecast_at(boundaries, 33.5, 136.5).is_none());
+
+        // 東京地方,東京を大阪府,大阪に置き換えた電文
+        let report: Report = R1
+            .replace("<Code>130010</Code>", "<Code>270000</Code>")
+            .replace("<Code>44132</Code>", "<Code>62078</Code>")
+            .parse()
+            .unwrap();
+        let osaka = report.forecast_at(boundaries, 34.69, 135.50).unwrap();
+        assert_eq!(osaka.area.area.code, 270000);
+        assert_eq!(osaka.station.unwrap().station.code, 62078);
+        assert!(report.forecast_at(boundaries, 35.69, 139.70).is_none());
+    }
+
+    #[test]
+    fn contains_multiple_polygons() {
+        let boundaries = Boundaries::from_csv(
+            "# version: test\nclass10,1,0 0;0 1;1 1;1 0\nclass10,1,5 5;5 6;6 6\n",
+        )
+        .unwrap();
+        assert_eq!(boundaries.version(), "test");
+        assert_eq!(boundaries.boundaries().len(), 1);
+        assert!(boundaries.boundaries()[0].contains(point(0.5, 0.5)));
+        assert!(boundaries.boundaries()[0].contains(point(5.2, 5.5)));
+        assert!(!boundaries.boundaries()[0].contains(point(3.0, 3.0)));
+        assert!(Boundaries::from_csv("class10,1,0 0;0 1").is_err());
+
+        let report: Report = R1.parse().unwrap();
+        let boundaries =
+            Boundaries::from_csv("class10,130020,35.0 139.0;35.0 140.0;36.0 140.0;36.0 139.0")
+                .unwrap();
+        let forecast = report.forecast_at(&boundaries, 35.69, 139.70).unwrap();
+        assert_eq!(forecast.area.area.code, 130020);
+        assert_eq!(forecast.station.unwrap().station.code, 44132);
+    }
+}
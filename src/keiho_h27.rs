@@ -0,0 +1,838 @@
+//! 気象警報・注意報（Ｈ２７）
+
+use chrono::{DateTime, FixedOffset};
+use serde_derive::Deserialize;
+#[cfg(feature = "serialize")]
+use serde_derive::Serialize;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::de;
+use crate::error::ParseError;
+use crate::fuken_r1::{Addition, Area, Control, Head, TimeDefine};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Report {
+    pub control: Control,
+    pub head: Head,
+    /// 付加事項
+    pub addition: Option<Addition>,
+    /// 警報・注意報の発表状況
+    pub warnings: Vec<Warning>,
+    /// 危険度,ピーク値等の時系列
+    pub time_series: Vec<AreaTimeSeries>,
+}
+
+impl Report {
+    fn from_tmp(tmp: ReportTmp) -> Result<Self, ParseError> {
+        let ReportTmp {
+            control,
+            head,
+            addition,
+            body:
+                BodyTmp {
+                    warning,
+                    meteorological_infos,
+                },
+        } = tmp;
+        let warnings = warning
+            .into_iter()
+            .map(Warning::from_tmp)
+            .collect::<Result<_, _>>()?;
+        let mut time_series = Vec::new();
+        for MeteorologicalInfosTmp { time_series_info } in meteorological_infos {
+            for TimeSeriesInfo {
+                time_defines:
+                    TimeDefines {
+                        items: mut time_defines,
+                    },
+                items,
+            } in time_series_info
+            {
+                time_defines.sort_by_key(|t| t.time_id);
+                for item in items {
+                    time_series.push(AreaTimeSeries::from_tmp(item, &time_defines)?);
+                }
+            }
+        }
+        Ok(Report {
+            control,
+            head,
+            addition,
+            warnings,
+            time_series,
+        })
+    }
+}
+
+impl FromStr for Report {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tmp: ReportTmp = de::from_str(s)?;
+        Report::from_tmp(tmp)
+    }
+}
+
+impl Report {
+    /// 読み込み元から直接パースする
+    ///
+    /// 省けるのは入力全体を`String`に読み込むことだけで,
+    /// 電文全体の要素の木は[`FromStr`]と同じくメモリ上に作られる
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, ParseError> {
+        let tmp: ReportTmp = de::from_reader(reader)?;
+        Report::from_tmp(tmp)
+    }
+
+    /// 読み込み済みの要素の木からパースする
+    pub(crate) fn from_document(document: &de::Document) -> Result<Self, ParseError> {
+        Report::from_tmp(document.deserialize()?)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Warning {
+    pub area_type: WarningAreaType,
+    pub items: Vec<WarningItem>,
+}
+
+impl Warning {
+    fn from_tmp(WarningTmp { _type, items }: WarningTmp) -> Result<Self, ParseError> {
+        let area_type = match _type.as_str() {
+            "気象警報・注意報（府県予報区等）" => WarningAreaType::Prefecture,
+            "気象警報・注意報（一次細分区域等）" => {
+                WarningAreaType::PrimarySubdivision
+            }
+            "気象警報・注意報（市町村等をまとめた地域等）" => {
+                WarningAreaType::MunicipalityGroup
+            }
+            "気象警報・注意報（市町村等）" => WarningAreaType::Municipality,
+            _ => {
+                return Err(ParseError::InvalidValue {
+                    path: "Body/Warning@type".into(),
+                    value: _type,
+                })
+            }
+        };
+        Ok(Warning { area_type, items })
+    }
+}
+
+/// 警報・注意報の対象とする区域の種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum WarningAreaType {
+    /// 府県予報区等
+    Prefecture,
+    /// 一次細分区域等
+    PrimarySubdivision,
+    /// 市町村等をまとめた地域等
+    MunicipalityGroup,
+    /// 市町村等
+    Municipality,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct WarningItem {
+    #[serde(alias = "Kind")]
+    pub kinds: Vec<WarningKind>,
+    #[serde(alias = "Area")]
+    pub area: Area,
+    /// 警報・注意報種別の変化の有無
+    #[serde(alias = "ChangeStatus")]
+    pub change_status: Option<String>,
+    /// 全域か一部か
+    #[serde(alias = "FullStatus")]
+    pub full_status: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct WarningKind {
+    #[serde(alias = "Name")]
+    pub name: Option<String>,
+    #[serde(alias = "Code")]
+    pub code: Option<u32>,
+    #[serde(alias = "Status")]
+    pub status: KindStatus,
+    /// 大雨警報の土砂災害,浸水害等の警戒事項
+    #[serde(alias = "Condition")]
+    pub condition: Option<String>,
+    /// 特別警報から警報への切り替え時等の直前の種別
+    #[serde(alias = "LastKind")]
+    pub last_kind: Option<KindName>,
+    /// 注意警戒事項
+    #[serde(alias = "Attention")]
+    pub attention: Option<Notes>,
+    /// 付加事項
+    #[serde(alias = "Addition")]
+    pub addition: Option<Notes>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub enum KindStatus {
+    #[serde(alias = "発表")]
+    Announcement,
+    #[serde(alias = "継続")]
+    Continuation,
+    #[serde(alias = "解除")]
+    Cancellation,
+    #[serde(alias = "特別警報から警報")]
+    EmergencyToWarning,
+    #[serde(alias = "特別警報から注意報")]
+    EmergencyToAdvisory,
+    #[serde(alias = "警報から注意報")]
+    WarningToAdvisory,
+    #[serde(alias = "発表警報・注意報はなし")]
+    NoWarning,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct KindName {
+    #[serde(alias = "Name")]
+    pub name: String,
+    #[serde(alias = "Code")]
+    pub code: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct Notes {
+    #[serde(alias = "Note")]
+    pub notes: Vec<String>,
+}
+
+/// 区域毎の時系列
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct AreaTimeSeries {
+    pub area: Area,
+    pub kinds: Vec<KindTimeSeries>,
+}
+
+impl AreaTimeSeries {
+    fn from_tmp(
+        Item { kind, area }: Item,
+        time_defines: &[TimeDefine],
+    ) -> Result<Self, ParseError> {
+        let kinds = kind
+            .into_iter()
+            .map(|kind| KindTimeSeries::from_tmp(kind, time_defines))
+            .collect::<Result<_, _>>()?;
+        Ok(AreaTimeSeries { area, kinds })
+    }
+}
+
+/// 警報・注意報の種別毎の時系列
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct KindTimeSeries {
+    pub name: Option<String>,
+    pub code: Option<u32>,
+    pub properties: Vec<PropertyTimeSeries>,
+    /// 今後の警報・注意報の切り替え
+    pub next_kinds: Vec<NextKind>,
+}
+
+impl KindTimeSeries {
+    fn from_tmp(
+        KindTmp {
+            name,
+            code,
+            property,
+            next_kinds,
+        }: KindTmp,
+        time_defines: &[TimeDefine],
+    ) -> Result<Self, ParseError> {
+        let properties = property
+            .into_iter()
+            .map(|property| PropertyTimeSeries::from_tmp(property, time_defines))
+            .collect::<Result<_, _>>()?;
+        Ok(KindTimeSeries {
+            name,
+            code,
+            properties,
+            next_kinds: next_kinds.map(|n| n.items).unwrap_or_default(),
+        })
+    }
+}
+
+/// 要素(雨,風,波等)毎の時系列
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct PropertyTimeSeries {
+    #[cfg_attr(feature = "serialize", serde(rename = "type"))]
+    pub _type: String,
+    /// 時間帯毎の危険度
+    pub significancy: Vec<SignificancyStep>,
+    /// 1時間最大雨量,最大風速等のピーク値
+    pub peaks: Vec<PeakValue>,
+}
+
+impl PropertyTimeSeries {
+    fn from_tmp(property: Property, time_defines: &[TimeDefine]) -> Result<Self, ParseError> {
+        let Property {
+            _type,
+            significancy_part,
+            precipitation_part,
+            wind_speed_part,
+            wave_height_part,
+            snowfall_depth_part,
+        } = property;
+        let find_time = |ref_id: usize, part: &'static str| {
+            time_defines
+                .iter()
+                .find(|t| t.time_id == ref_id)
+                .cloned()
+                .ok_or_else(|| ParseError::InvalidValue {
+                    path: format!(
+                        "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/{}@refID",
+                        part
+                    )
+                    .into(),
+                    value: ref_id.to_string(),
+                })
+        };
+        let significancy = match significancy_part {
+            Some(part) => part
+                .flatten("SignificancyPart")?
+                .into_iter()
+                .map(|(SignificancyTmp { ref_id, name, code }, scope)| {
+                    Ok(SignificancyStep {
+                        time: find_time(ref_id, "SignificancyPart")?,
+                        level: SignificancyLevel::from_name(&name),
+                        name,
+                        code,
+                        scope,
+                    })
+                })
+                .collect::<Result<_, ParseError>>()?,
+            None => Vec::new(),
+        };
+        let mut peaks = Vec::new();
+        for (kind, part, name) in [
+            (
+                PeakKind::Precipitation,
+                precipitation_part,
+                "PrecipitationPart",
+            ),
+            (PeakKind::WindSpeed, wind_speed_part, "WindSpeedPart"),
+            (PeakKind::WaveHeight, wave_height_part, "WaveHeightPart"),
+            (
+                PeakKind::SnowfallDepth,
+                snowfall_depth_part,
+                "SnowfallDepthPart",
+            ),
+        ] {
+            let Some(part) = part else {
+                continue;
+            };
+            for (value, scope) in part.flatten(name)? {
+                peaks.push(PeakValue {
+                    kind,
+                    time: value.ref_id.map(|r| find_time(r, name)).transpose()?,
+                    value: value.parse()?,
+                    _type: value._type,
+                    unit: value.unit,
+                    description: value.description,
+                    scope,
+                });
+            }
+        }
+        Ok(PropertyTimeSeries {
+            _type,
+            significancy,
+            peaks,
+        })
+    }
+}
+
+/// 値が`Base`,`Temporary`,`SubArea`のどこに含まれていたか
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct PartScope {
+    /// `Temporary`(一時的な状態)に含まれる値か
+    pub temporary: bool,
+    /// `SubArea`に含まれる値であればその`AreaName`(陸上,海上等)
+    pub sub_area: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct SignificancyStep {
+    pub time: TimeDefine,
+    pub level: Option<SignificancyLevel>,
+    pub name: String,
+    pub code: Option<u32>,
+    pub scope: PartScope,
+}
+
+/// 危険度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum SignificancyLevel {
+    /// 注意報級
+    Advisory,
+    /// 警報級
+    Warning,
+    /// 特別警報級
+    Emergency,
+}
+
+impl SignificancyLevel {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "注意報級" => Some(SignificancyLevel::Advisory),
+            "警報級" => Some(SignificancyLevel::Warning),
+            "特別警報級" => Some(SignificancyLevel::Emergency),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct PeakValue {
+    pub kind: PeakKind,
+    pub time: Option<TimeDefine>,
+    /// １時間最大雨量,最大風速等
+    #[cfg_attr(feature = "serialize", serde(rename = "type"))]
+    pub _type: String,
+    pub unit: Option<String>,
+    pub value: Option<f64>,
+    pub description: Option<String>,
+    pub scope: PartScope,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum PeakKind {
+    Precipitation,
+    WindSpeed,
+    WaveHeight,
+    SnowfallDepth,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct NextKind {
+    #[serde(alias = "Name")]
+    pub name: String,
+    #[serde(alias = "Code")]
+    pub code: Option<u32>,
+    /// 切り替えの見込み時刻
+    #[serde(alias = "DateTime")]
+    #[serde(rename(serialize = "datetime"), alias = "datetime")]
+    pub date_time: Option<DateTime<FixedOffset>>,
+    #[serde(alias = "Sentence")]
+    pub sentence: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ReportTmp {
+    #[serde(alias = "Control")]
+    control: Control,
+    #[serde(alias = "Head")]
+    head: Head,
+    #[serde(alias = "Body")]
+    body: BodyTmp,
+    #[serde(alias = "jmx_add:Addition")]
+    addition: Option<Addition>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct BodyTmp {
+    #[serde(alias = "Warning")]
+    warning: Vec<WarningTmp>,
+    /// 危険度等の時系列が無い電文もある
+    #[serde(alias = "MeteorologicalInfos")]
+    #[serde(default)]
+    meteorological_infos: Vec<MeteorologicalInfosTmp>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct WarningTmp {
+    #[serde(alias = "type")]
+    _type: String,
+    #[serde(alias = "Item")]
+    items: Vec<WarningItem>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct MeteorologicalInfosTmp {
+    #[serde(alias = "TimeSeriesInfo")]
+    time_series_info: Vec<TimeSeriesInfo>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TimeSeriesInfo {
+    #[serde(alias = "TimeDefines")]
+    time_defines: TimeDefines,
+    #[serde(alias = "Item")]
+    items: Vec<Item>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TimeDefines {
+    #[serde(alias = "TimeDefine")]
+    items: Vec<TimeDefine>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Item {
+    #[serde(alias = "Kind")]
+    kind: Vec<KindTmp>,
+    #[serde(alias = "Area")]
+    area: Area,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct KindTmp {
+    #[serde(alias = "Name")]
+    name: Option<String>,
+    #[serde(alias = "Code")]
+    code: Option<u32>,
+    #[serde(alias = "Property")]
+    property: Vec<Property>,
+    #[serde(alias = "NextKinds")]
+    next_kinds: Option<NextKinds>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct NextKinds {
+    #[serde(alias = "NextKind")]
+    items: Vec<NextKind>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Property {
+    #[serde(alias = "Type")]
+    _type: String,
+    #[serde(alias = "SignificancyPart")]
+    significancy_part: Option<Part<SignificancyTmp>>,
+    #[serde(alias = "PrecipitationPart")]
+    precipitation_part: Option<Part<Value>>,
+    #[serde(alias = "WindSpeedPart")]
+    wind_speed_part: Option<Part<Value>>,
+    #[serde(alias = "WaveHeightPart")]
+    wave_height_part: Option<Part<Value>>,
+    #[serde(alias = "SnowfallDepthPart")]
+    snowfall_depth_part: Option<Part<Value>>,
+}
+
+/// `SignificancyPart`,`PrecipitationPart`等
+///
+/// 値は直下に並ぶか,`Base`,`Temporary`,`SubArea`の中に並ぶ
+#[derive(Debug, Clone, Deserialize)]
+struct Part<T> {
+    #[serde(alias = "Significancy")]
+    #[serde(alias = "jmx_eb:Precipitation")]
+    #[serde(alias = "jmx_eb:WindSpeed")]
+    #[serde(alias = "jmx_eb:WaveHeight")]
+    #[serde(alias = "jmx_eb:SnowfallDepth")]
+    #[serde(default = "Vec::new")]
+    values: Vec<T>,
+    #[serde(alias = "Base")]
+    base: Option<Box<Part<T>>>,
+    #[serde(alias = "Temporary")]
+    #[serde(default = "Vec::new")]
+    temporary: Vec<Part<T>>,
+    #[serde(alias = "SubArea")]
+    #[serde(default = "Vec::new")]
+    sub_areas: Vec<Part<T>>,
+    #[serde(alias = "AreaName")]
+    area_name: Option<String>,
+}
+
+impl<T> Part<T> {
+    /// 入れ子を展開して値とその範囲を返す,値が1つも無ければエラー
+    fn flatten(self, name: &'static str) -> Result<Vec<(T, PartScope)>, ParseError> {
+        let mut values = Vec::new();
+        self.flatten_into(PartScope::default(), &mut values);
+        if values.is_empty() {
+            return Err(ParseError::MissingElement {
+                path: format!(
+                    "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/{}",
+                    name
+                )
+                .into(),
+            });
+        }
+        Ok(values)
+    }
+
+    fn flatten_into(self, mut scope: PartScope, values: &mut Vec<(T, PartScope)>) {
+        if self.area_name.is_some() {
+            scope.sub_area = self.area_name;
+        }
+        values.extend(self.values.into_iter().map(|v| (v, scope.clone())));
+        if let Some(base) = self.base {
+            base.flatten_into(scope.clone(), values);
+        }
+        for temporary in self.temporary {
+            let scope = PartScope {
+                temporary: true,
+                ..scope.clone()
+            };
+            temporary.flatten_into(scope, values);
+        }
+        for sub_area in self.sub_areas {
+            sub_area.flatten_into(scope.clone(), values);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SignificancyTmp {
+    #[serde(alias = "refID")]
+    ref_id: usize,
+    #[serde(alias = "Name")]
+    name: String,
+    #[serde(alias = "Code")]
+    code: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Value {
+    #[serde(alias = "refID")]
+    ref_id: Option<usize>,
+    #[serde(alias = "type")]
+    _type: String,
+    unit: Option<String>,
+    description: Option<String>,
+    #[serde(alias = "$value")]
+    #[serde(default)]
+    value: Option<String>,
+}
+
+impl Value {
+    fn parse(&self) -> Result<Option<f64>, ParseError> {
+        match self.value.as_deref().map(str::trim) {
+            None | Some("") => Ok(None),
+            Some(v) => v.parse().map(Some).map_err(|_| ParseError::InvalidValue {
+                path: "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property".into(),
+                value: v.to_owned(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VPWW54: &str = include_str!("../benches/data/vpww54.xml");
+    const KEIHO: &str = include_str!("../benches/data/keiho.xml");
+
+    fn scope(temporary: bool, sub_area: Option<&str>) -> PartScope {
+        PartScope {
+            temporary,
+            sub_area: sub_area.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn parse_warnings() {
+        let report: Report = VPWW54.parse().unwrap();
+        let area_types = report
+            .warnings
+            .iter()
+            .map(|w| w.area_type)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            area_types,
+            [
+                WarningAreaType::Prefecture,
+                WarningAreaType::PrimarySubdivision,
+                WarningAreaType::MunicipalityGroup,
+                WarningAreaType::Municipality
+            ]
+        );
+        assert_eq!(report.warnings[1].items.len(), 2);
+
+        let item = &report.warnings[3].items[0];
+        assert_eq!(item.area.code, 1330800);
+        assert_eq!(item.full_status.as_deref(), Some("一部"));
+        let kind = &item.kinds[0];
+        assert_eq!(kind.code, Some(3));
+        assert!(matches!(kind.status, KindStatus::Announcement));
+        assert_eq!(kind.condition.as_deref(), Some("土砂災害"));
+        assert_eq!(kind.attention.as_ref().unwrap().notes, ["土砂災害警戒"]);
+        assert!(matches!(item.kinds[1].status, KindStatus::Continuation));
+    }
+
+    #[test]
+    fn parse_nested_time_series() {
+        let report: Report = VPWW54.parse().unwrap();
+        assert_eq!(report.time_series.len(), 1);
+        let series = &report.time_series[0];
+        assert_eq!(series.area.code, 1330800);
+        assert_eq!(series.kinds.len(), 2);
+
+        let rain = &series.kinds[0];
+        assert_eq!(rain.code, Some(3));
+        let types = rain
+            .properties
+            .iter()
+            .map(|p| p._type.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(types, ["土砂災害", "雨"]);
+        let levels = rain.properties[1]
+            .significancy
+            .iter()
+            .map(|s| (s.time.time_id, s.level))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            levels,
+            [
+                (1, Some(SignificancyLevel::Warning)),
+                (2, Some(SignificancyLevel::Advisory)),
+                (3, None)
+            ]
+        );
+        let peaks = rain.properties[1]
+            .peaks
+            .iter()
+            .map(|p| (p.kind, p.time.as_ref().unwrap().time_id, p.value))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            peaks,
+            [
+                (PeakKind::Precipitation, 1, Some(50.0)),
+                (PeakKind::Precipitation, 2, Some(30.0)),
+                (PeakKind::Precipitation, 3, None)
+            ]
+        );
+        assert!(rain.properties[1]
+            .peaks
+            .iter()
+            .all(|p| p.scope == PartScope::default()));
+        assert_eq!(rain.next_kinds.len(), 1);
+        assert_eq!(rain.next_kinds[0].code, Some(10));
+
+        let wind = &series.kinds[1].properties[0];
+        let significancy = wind
+            .significancy
+            .iter()
+            .map(|s| (s.time.time_id, s.level, s.scope.temporary))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            significancy,
+            [
+                (1, Some(SignificancyLevel::Advisory), false),
+                (2, Some(SignificancyLevel::Advisory), false),
+                (3, None, false),
+                (3, Some(SignificancyLevel::Advisory), true)
+            ]
+        );
+        let peaks = wind
+            .peaks
+            .iter()
+            .map(|p| (p.kind, p.value, p.scope.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            peaks,
+            [
+                (PeakKind::WindSpeed, Some(12.0), scope(false, Some("陸上"))),
+                (PeakKind::WindSpeed, Some(12.0), scope(false, Some("陸上"))),
+                (PeakKind::WindSpeed, Some(15.0), scope(false, Some("海上"))),
+                (PeakKind::WindSpeed, Some(15.0), scope(false, Some("海上")))
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_flat_time_series() {
+        let report: Report = KEIHO.parse().unwrap();
+        let property = &report.time_series[0].kinds[0].properties[0];
+        assert_eq!(property.significancy.len(), 2);
+        assert_eq!(property.significancy[0].scope, PartScope::default());
+        assert_eq!(property.peaks[0].value, Some(80.0));
+        assert!(matches!(
+            report.warnings[1].items[1].kinds[0].status,
+            KindStatus::NoWarning
+        ));
+    }
+
+    #[test]
+    fn unmatched_ref_id() {
+        let error = VPWW54
+            .replace(
+                r#"<Significancy type="雨" refID="3">"#,
+                r#"<Significancy type="雨" refID="4">"#,
+            )
+            .parse::<Report>()
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            ParseError::InvalidValue { ref path, ref value }
+                if path == "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/SignificancyPart@refID"
+                    && value == "4"
+        ));
+
+        let error = VPWW54
+            .replace(r#"unit="m/s" refID="2""#, r#"unit="m/s" refID="9""#)
+            .parse::<Report>()
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            ParseError::InvalidValue { ref path, .. }
+                if path == "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/WindSpeedPart@refID"
+        ));
+    }
+
+    #[test]
+    fn structural_mismatch_is_an_error() {
+        // Kindの無いItem
+        let error = VPWW54
+            .replace(
+                "<Item>\n<Kind><Name>強風注意報</Name><Code>15</Code><Status>継続</Status></Kind>\n<Area><Name>伊豆諸島北部</Name>",
+                "<Item>\n<Area><Name>伊豆諸島北部</Name>",
+            )
+            .parse::<Report>()
+            .unwrap_err();
+        assert!(
+            matches!(error, ParseError::MissingElement { ref path } if path.starts_with("Body/Warning/Item/")),
+            "{:?}",
+            error
+        );
+
+        // 値の無いSignificancyPart
+        let start = VPWW54.find("<Base>\n<Significancy type=\"雨\"").unwrap();
+        let end = start + VPWW54[start..].find("</Base>").unwrap() + "</Base>".len();
+        let error = format!("{}{}", &VPWW54[..start], &VPWW54[end..])
+            .parse::<Report>()
+            .unwrap_err();
+        assert!(
+            matches!(error, ParseError::MissingElement { ref path }
+                if path == "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/SignificancyPart"),
+            "{:?}",
+            error
+        );
+    }
+
+    #[test]
+    fn root_addition() {
+        let report: Report = VPWW54.parse().unwrap();
+        assert_eq!(
+            report.addition.unwrap().notices,
+            [
+                "令和３年１０月１日から東京都の大雨警報（土砂災害）の発表基準を変更しました。",
+                "詳しくは気象庁ホームページをご覧ください。",
+            ]
+        );
+        let kind = &report.warnings[3].items[0].kinds[1];
+        assert_eq!(kind.addition.as_ref().unwrap().notes, ["竜巻"]);
+
+        // Kind/Additionは電文の付加事項にならない
+        let report: Report = KEIHO.parse().unwrap();
+        assert!(report.addition.is_none());
+        let kind = &report.warnings[1].items[0].kinds[0];
+        assert_eq!(kind.addition.as_ref().unwrap().notes, ["竜巻"]);
+    }
+}
@@ -1,3 +1,45 @@
+//! 気象庁防災情報XMLのパーサ
+//!
+//! # Cargo features
+//!
+//! - `http`: reqwestによる[`fetch::HttpFetcher`],[`fetch::AsyncHttpFetcher`]
+//! - `serialize`: パース結果の型に`Serialize`(とXML由来でない型には`Deserialize`)を実装する。
+//!   フィールド名はRustのフィールド名そのままで,`_type`は`type`,
+//!   日時の`date_time`は他のフィールドに合わせて`datetime`になる。
+//!   フィードの`entry`は`entries`,`$value`は`value`/`item`になる。
+
+mod de;
+pub mod document;
+/// ISO 8601の期間
+pub mod duration;
+pub mod error;
 pub mod feed;
+pub mod fetch;
 /// 府県天気予報（Ｒ１）
 pub mod fuken_r1;
+/// 緯度,経度からの区域,地点の検索
+pub mod geo;
+/// 気象警報・注意報（Ｈ２７）
+pub mod keiho_h27;
+pub mod poller;
+/// 独自予報
+pub mod proprietary;
+/// 地域コード
+pub mod registry;
+/// 天気,風,波の予報文
+pub mod sentence;
+/// 震源・震度に関する情報
+pub mod shingen_shindo;
+/// 府県週間天気予報
+pub mod shukan;
+/// 日時の変換
+pub mod time;
+/// 津波警報・注意報・予報,津波情報
+pub mod tsunami;
+/// 天気コード
+pub mod weather_code;
+/// 風向,風速階級
+pub mod wind;
+
+pub use document::{parse_document, JmaDocument};
+pub use error::{DurationError, ParseError, RegistryError, XmlError};
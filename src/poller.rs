@@ -0,0 +1,270 @@
+//! フィードの定期取得と既読管理
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use chrono::{DateTime, Duration, FixedOffset};
+
+use crate::feed::{Entry, Feed};
+use crate::fetch::{FeedFetcher, FeedRequest, FeedResponse, FetchError};
+
+/// 同じフィードを繰り返し取得して新しいEntryだけを返す
+///
+/// 初回の取得ではフィードに含まれる全てのEntryが新しいものとして返される
+#[derive(Debug, Clone)]
+pub struct FeedPoller {
+    url: String,
+    long_url: Option<String>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    seen: SeenEntries,
+}
+
+impl FeedPoller {
+    /// 既読のidを最大1000件,最新のEntryから1日分保持する
+    pub fn new(url: impl Into<String>) -> Self {
+        let url = url.into();
+        let long_url = long_feed_url(&url);
+        FeedPoller {
+            url,
+            long_url,
+            etag: None,
+            last_modified: None,
+            seen: SeenEntries::new(1000, Duration::days(1)),
+        }
+    }
+
+    /// 既読のidを保持する最大件数
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.seen.capacity = capacity;
+        self
+    }
+
+    /// 最新のEntryのupdatedからどれだけ前までの既読のidを保持するか
+    pub fn with_retention(mut self, retention: Duration) -> Self {
+        self.seen.retention = retention;
+        self
+    }
+
+    /// 取りこぼしを検出したときに取得する長期フィードのURL
+    ///
+    /// 既定では`regular.xml`に対する`regular_l.xml`のように推測される
+    pub fn with_long_url(mut self, long_url: Option<String>) -> Self {
+        self.long_url = long_url;
+        self
+    }
+
+    /// フィードを取得して新しいEntryを古い順に返す
+    pub fn poll<F: FeedFetcher + ?Sized>(&mut self, fetcher: &F) -> Result<Vec<Entry>, FetchError> {
+        let response = fetcher.fetch_feed(&FeedRequest {
+            url: &self.url,
+            etag: self.etag.as_deref(),
+            last_modified: self.last_modified.as_deref(),
+        })?;
+        let (body, etag, last_modified) = match response {
+            FeedResponse::NotModified => return Ok(Vec::new()),
+            FeedResponse::Modified {
+                body,
+                etag,
+                last_modified,
+            } => (body, etag, last_modified),
+        };
+        let feed: Feed = body.parse()?;
+        let gap = self.has_gap(&feed.entries);
+        let mut entries = self.unseen(feed.entries);
+        if let (Some(long_url), Some(latest), true) = (&self.long_url, self.seen.latest, gap) {
+            let response = fetcher.fetch_feed(&FeedRequest {
+                url: long_url,
+                etag: None,
+                last_modified: None,
+            })?;
+            if let FeedResponse::Modified { body, .. } = response {
+                // 既読の記録から外れた古いEntryを再び返さないよう,既読の最新のEntry以降に限る
+                let feed: Feed = body.parse()?;
+                let missed = self
+                    .unseen(feed.entries)
+                    .into_iter()
+                    .filter(|e| e.updated >= latest);
+                entries.extend(missed);
+            }
+        }
+        let mut ids = HashSet::new();
+        entries.retain(|e| ids.insert(e.id.clone()));
+        entries.sort_by_key(|e| e.updated);
+        for entry in &entries {
+            self.seen.insert(&entry.id, entry.updated);
+        }
+        self.etag = etag;
+        self.last_modified = last_modified;
+        Ok(entries)
+    }
+
+    fn unseen(&self, entries: Vec<Entry>) -> Vec<Entry> {
+        entries
+            .into_iter()
+            .filter(|e| !self.seen.contains(&e.id))
+            .collect()
+    }
+
+    /// フィードが全て未読で,最も古いEntryが既読の最新のEntryより新しければ間が抜けている可能性がある
+    fn has_gap(&self, entries: &[Entry]) -> bool {
+        if entries.iter().any(|e| self.seen.contains(&e.id)) {
+            return false;
+        }
+        match (self.seen.latest, entries.iter().map(|e| e.updated).min()) {
+            (Some(latest), Some(oldest)) => oldest > latest,
+            _ => false,
+        }
+    }
+}
+
+/// `regular.xml`に対する`regular_l.xml`
+fn long_feed_url(url: &str) -> Option<String> {
+    let stem = url.strip_suffix(".xml")?;
+    if stem.ends_with("_l") {
+        return None;
+    }
+    Some(format!("{}_l.xml", stem))
+}
+
+#[derive(Debug, Clone)]
+struct SeenEntries {
+    capacity: usize,
+    retention: Duration,
+    order: VecDeque<(String, DateTime<FixedOffset>)>,
+    ids: HashMap<String, DateTime<FixedOffset>>,
+    latest: Option<DateTime<FixedOffset>>,
+}
+
+impl SeenEntries {
+    fn new(capacity: usize, retention: Duration) -> Self {
+        SeenEntries {
+            capacity,
+            retention,
+            order: VecDeque::new(),
+            ids: HashMap::new(),
+            latest: None,
+        }
+    }
+
+    fn contains(&self, id: &str) -> bool {
+        self.ids.contains_key(id)
+    }
+
+    fn insert(&mut self, id: &str, updated: DateTime<FixedOffset>) {
+        if self.ids.insert(id.to_owned(), updated).is_none() {
+            self.order.push_back((id.to_owned(), updated));
+        }
+        let latest = match self.latest {
+            Some(latest) if latest >= updated => latest,
+            _ => updated,
+        };
+        self.latest = Some(latest);
+        let expire = latest - self.retention;
+        while let Some((id, updated)) = self.order.front() {
+            if self.order.len() <= self.capacity && *updated >= expire {
+                break;
+            }
+            self.ids.remove(id);
+            self.order.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    /// 決められた順に決められたレスポンスを返す
+    struct StubFetcher {
+        responses: RefCell<VecDeque<(&'static str, FeedResponse)>>,
+    }
+
+    impl StubFetcher {
+        fn new(responses: Vec<(&'static str, FeedResponse)>) -> Self {
+            StubFetcher {
+                responses: RefCell::new(responses.into()),
+            }
+        }
+    }
+
+    impl FeedFetcher for StubFetcher {
+        fn fetch_feed(&self, request: &FeedRequest<'_>) -> Result<FeedResponse, FetchError> {
+            let (url, response) = self
+                .responses
+                .borrow_mut()
+                .pop_front()
+                .expect("unexpected request");
+            assert_eq!(request.url, url);
+            Ok(response)
+        }
+    }
+
+    const REGULAR: &str = "https://www.data.jma.go.jp/developer/xml/feed/regular.xml";
+    const REGULAR_L: &str = "https://www.data.jma.go.jp/developer/xml/feed/regular_l.xml";
+
+    /// idとupdatedの時刻からなるフィード
+    fn feed(entries: &[(&str, &str)]) -> FeedResponse {
+        let entries = entries
+            .iter()
+            .map(|(id, time)| {
+                format!(
+                    r#"<entry><title>t</title><id>{}</id><updated>2021-10-01T{}+09:00</updated><author><name>気象庁</name></author><link type="application/xml" href="https://www.data.jma.go.jp/developer/xml/data/{}.xml"/><content type="text">c</content></entry>"#,
+                    id, time, id
+                )
+            })
+            .collect::<String>();
+        FeedResponse::Modified {
+            body: format!(
+                r#"<feed xmlns="http://www.w3.org/2005/Atom" lang="ja"><title>t</title><subtitle>s</subtitle><updated>2021-10-01T12:00:00+09:00</updated><id>feed</id><link rel="self" href="{}"/><rights type="html">r</rights>{}</feed>"#,
+                REGULAR, entries
+            ),
+            etag: Some("\"etag\"".to_owned()),
+            last_modified: None,
+        }
+    }
+
+    fn ids(entries: &[Entry]) -> Vec<&str> {
+        entries.iter().map(|e| e.id.as_str()).collect()
+    }
+
+    #[test]
+    fn poll_new_entries_in_order() {
+        let fetcher = StubFetcher::new(vec![
+            (REGULAR, feed(&[("b", "10:01:00"), ("a", "10:00:00")])),
+            (REGULAR, FeedResponse::NotModified),
+            (REGULAR, feed(&[("c", "10:02:00"), ("b", "10:01:00")])),
+        ]);
+        let mut poller = FeedPoller::new(REGULAR);
+        assert_eq!(ids(&poller.poll(&fetcher).unwrap()), ["a", "b"]);
+        assert_eq!(poller.etag.as_deref(), Some("\"etag\""));
+        assert!(poller.poll(&fetcher).unwrap().is_empty());
+        assert_eq!(ids(&poller.poll(&fetcher).unwrap()), ["c"]);
+        assert!(fetcher.responses.borrow().is_empty());
+    }
+
+    #[test]
+    fn fill_gap_from_long_feed() {
+        let fetcher = StubFetcher::new(vec![
+            (REGULAR, feed(&[("b", "10:01:00"), ("a", "10:00:00")])),
+            (REGULAR, feed(&[("e", "10:04:00"), ("d", "10:03:00")])),
+            (
+                REGULAR_L,
+                feed(&[
+                    ("e", "10:04:00"),
+                    ("d", "10:03:00"),
+                    ("c", "10:02:00"),
+                    ("c2", "10:01:00"),
+                    ("b", "10:01:00"),
+                    ("a", "10:00:00"),
+                ]),
+            ),
+        ]);
+        // aは既読の記録から外れるが,長期フィードから再び返されてはいけない
+        let mut poller = FeedPoller::new(REGULAR).with_capacity(1);
+        assert_eq!(ids(&poller.poll(&fetcher).unwrap()), ["a", "b"]);
+        assert_eq!(ids(&poller.poll(&fetcher).unwrap()), ["c2", "c", "d", "e"]);
+        assert!(fetcher.responses.borrow().is_empty());
+    }
+}
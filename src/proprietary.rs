@@ -0,0 +1,190 @@
+//! 独自予報
+
+#[cfg(feature = "serialize")]
+use serde_derive::{Deserialize, Serialize};
+
+use crate::fuken_r1::{MeteorologicalInfo, MeteorologicalInfoProperty, Report};
+use crate::sentence::normalize;
+
+/// 独自予報のPropertyの内容
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum ProprietaryContent {
+    /// 天気概況
+    WeatherOverview(String),
+    /// 地域名
+    RegionName(String),
+    /// 降水量
+    Precipitation {
+        text: String,
+        /// 本文中の最初の「〜ミリ」(mm)
+        amount: Option<f64>,
+    },
+    Other {
+        #[cfg_attr(feature = "serialize", serde(rename = "type"))]
+        _type: String,
+        text: String,
+    },
+}
+
+impl MeteorologicalInfoProperty {
+    pub fn content(&self) -> ProprietaryContent {
+        let text = self.text.clone();
+        match self._type.as_str() {
+            "天気概況" => ProprietaryContent::WeatherOverview(text),
+            "地域名" => ProprietaryContent::RegionName(text),
+            "降水量" => ProprietaryContent::Precipitation {
+                amount: millimeters(&text),
+                text,
+            },
+            _ => ProprietaryContent::Other {
+                _type: self._type.clone(),
+                text,
+            },
+        }
+    }
+}
+
+impl MeteorologicalInfo {
+    pub fn content(&self) -> ProprietaryContent {
+        self.item.kind.property.content()
+    }
+}
+
+impl Report {
+    /// 天気概況,なければNone
+    pub fn weather_overview(&self) -> Option<&str> {
+        self.proprietary_forecasts
+            .iter()
+            .map(|info| &info.item.kind.property)
+            .find(|property| property._type == "天気概況")
+            .map(|property| property.text.as_str())
+    }
+}
+
+/// 「多い所で５０ミリ」等から50.0を取り出す
+fn millimeters(text: &str) -> Option<f64> {
+    let text = normalize(text);
+    let end = text.find("ミリ")?;
+    let digits = text[..end]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| c.is_ascii_digit() || *c == '.')
+        .last()
+        .map(|(i, _)| i)?;
+    text[digits..end].parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const R1: &str = include_str!("../benches/data/r1.xml");
+
+    fn property(_type: &str, text: &str) -> MeteorologicalInfoProperty {
+        MeteorologicalInfoProperty {
+            _type: _type.to_owned(),
+            text: text.to_owned(),
+        }
+    }
+
+    /// 独自予報のMeteorologicalInfoをTypeとTextの組で置き換える
+    fn with_proprietary(properties: &[(&str, &str)]) -> Report {
+        let start = R1.find("<MeteorologicalInfo>").unwrap();
+        let end = R1.rfind("</MeteorologicalInfo>").unwrap() + "</MeteorologicalInfo>".len();
+        let infos = properties
+            .iter()
+            .map(|(t, text)| {
+                format!(
+                    "<MeteorologicalInfo><DateTime>2021-10-01T11:00:00+09:00</DateTime><Duration>PT13H</Duration><Item><Kind><Property><Type>{}</Type><Text>{}</Text></Property></Kind><Area><Name>東京都</Name><Code>130000</Code></Area></Item></MeteorologicalInfo>",
+                    t, text
+                )
+            })
+            .collect::<String>();
+        format!("{}{}{}", &R1[..start], infos, &R1[end..])
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn content() {
+        let report = with_proprietary(&[
+            ("地域名", "東京地方"),
+            ("天気概況", "前線の影響で雨が降っています。"),
+            ("降水量", "多い所で５０ミリ"),
+            ("降水量", "多い所で１２．５ミリの見込み"),
+            ("降水量", "まとまった雨の見込みはありません"),
+            ("注意事項", "土砂災害に注意してください"),
+        ]);
+        let contents = report
+            .proprietary_forecasts
+            .iter()
+            .map(MeteorologicalInfo::content)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            contents,
+            [
+                ProprietaryContent::RegionName("東京地方".to_owned()),
+                ProprietaryContent::WeatherOverview("前線の影響で雨が降っています。".to_owned()),
+                ProprietaryContent::Precipitation {
+                    text: "多い所で５０ミリ".to_owned(),
+                    amount: Some(50.0),
+                },
+                ProprietaryContent::Precipitation {
+                    text: "多い所で１２．５ミリの見込み".to_owned(),
+                    amount: Some(12.5),
+                },
+                ProprietaryContent::Precipitation {
+                    text: "まとまった雨の見込みはありません".to_owned(),
+                    amount: None,
+                },
+                ProprietaryContent::Other {
+                    _type: "注意事項".to_owned(),
+                    text: "土砂災害に注意してください".to_owned(),
+                },
+            ]
+        );
+        assert_eq!(
+            property("", "").content(),
+            ProprietaryContent::Other {
+                _type: String::new(),
+                text: String::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn weather_overview() {
+        let report: Report = R1.parse().unwrap();
+        assert_eq!(
+            report.weather_overview(),
+            Some("前線の影響で雨が降っています。")
+        );
+
+        // 最初の天気概況
+        let report = with_proprietary(&[
+            ("地域名", "東京地方"),
+            ("天気概況", "晴れています。"),
+            ("天気概況", "くもっています。"),
+        ]);
+        assert_eq!(report.weather_overview(), Some("晴れています。"));
+
+        let report = with_proprietary(&[("地域名", "東京地方")]);
+        assert_eq!(report.weather_overview(), None);
+        let report = with_proprietary(&[]);
+        assert!(report.proprietary_forecasts.is_empty());
+        assert_eq!(report.weather_overview(), None);
+    }
+
+    #[test]
+    fn millimeters_in_text() {
+        assert_eq!(millimeters("多い所で５０ミリ"), Some(50.0));
+        assert_eq!(millimeters("多い所で50ミリ"), Some(50.0));
+        assert_eq!(millimeters("１．５ミリ"), Some(1.5));
+        assert_eq!(millimeters("１２０ミリ、その後３０ミリ"), Some(120.0));
+        assert_eq!(millimeters("まとまった雨の見込みはありません"), None);
+        assert_eq!(millimeters("数ミリ"), None);
+        assert_eq!(millimeters("ミリ"), None);
+        assert_eq!(millimeters(""), None);
+    }
+}
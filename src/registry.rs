@@ -0,0 +1,516 @@
+//! 気象庁の地域コードの一覧
+//!
+//! 同梱のデータ(`data/areas.csv`,バージョン`2021-10-partial`)は公式のコード表の一部で,
+//! 次のものしか含まない。
+//!
+//! - 府県予報区,一次細分区域: 全件
+//! - 市町村等をまとめた地域,市町村等: 東京都のみ
+//! - 気温予報地点: 各一次細分区域の主な地点(約60地点)のみ
+//! - 津波予報区,地震情報に用いる地域: 関東と大阪府の周辺のみで,親の区域,位置は無い
+//!
+//! 含まれないコードは[`get`](crate::registry::get)等でNoneになる。公式のコード表を同じ形式のCSVに変換すれば
+//! [`Registry::from_csv`](crate::registry::Registry::from_csv)で読み込める。
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+#[cfg(feature = "serialize")]
+use serde_derive::{Deserialize, Serialize};
+
+use crate::error::RegistryError;
+use crate::fuken_r1::{Area, Station};
+
+const BUNDLED_CSV: &str = include_str!("../data/areas.csv");
+
+/// 地域コードの種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum AreaKind {
+    /// 府県予報区
+    Prefecture,
+    /// 一次細分区域
+    PrimarySubdivision,
+    /// 市町村等をまとめた地域
+    MunicipalityGroup,
+    /// 市町村等
+    Municipality,
+    /// 気象官署,アメダス等の地点
+    Station,
+    /// 津波予報区
+    TsunamiForecastArea,
+    /// 地震情報に用いる地域
+    EarthquakeRegion,
+}
+
+impl AreaKind {
+    /// CSVのkind列の値から変換する
+    pub fn from_key(key: &str) -> Option<AreaKind> {
+        match key {
+            "office" => Some(AreaKind::Prefecture),
+            "class10" => Some(AreaKind::PrimarySubdivision),
+            "class15" => Some(AreaKind::MunicipalityGroup),
+            "class20" => Some(AreaKind::Municipality),
+            "station" => Some(AreaKind::Station),
+            "tsunami" => Some(AreaKind::TsunamiForecastArea),
+            "earthquake" => Some(AreaKind::EarthquakeRegion),
+            _ => None,
+        }
+    }
+
+    /// CSVのkind列の値
+    pub fn key(self) -> &'static str {
+        match self {
+            AreaKind::Prefecture => "office",
+            AreaKind::PrimarySubdivision => "class10",
+            AreaKind::MunicipalityGroup => "class15",
+            AreaKind::Municipality => "class20",
+            AreaKind::Station => "station",
+            AreaKind::TsunamiForecastArea => "tsunami",
+            AreaKind::EarthquakeRegion => "earthquake",
+        }
+    }
+}
+
+/// 緯度,経度(度)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Point {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// 地域コード表の1行
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct AreaEntry {
+    pub kind: AreaKind,
+    pub code: usize,
+    pub name: String,
+    /// 親の区域の種別とコード
+    pub parent: Option<(AreaKind, usize)>,
+    /// 地点では観測所の位置,区域では動作確認用の境界([`crate::geo::Boundaries::sample`])の重心,
+    /// 境界が無い区域ではNone
+    pub location: Option<Point>,
+}
+
+/// 地域コード表
+///
+/// 種別が異なれば同じコードが使われることがある(例えば011000)ため,
+/// 種別とコードの組で区別する。
+#[derive(Debug, Clone)]
+pub struct Registry {
+    version: String,
+    entries: Vec<AreaEntry>,
+    index: HashMap<(AreaKind, usize), usize>,
+    by_code: HashMap<usize, Vec<usize>>,
+    children: HashMap<(AreaKind, usize), Vec<usize>>,
+}
+
+impl Registry {
+    /// 同梱のコード表
+    ///
+    /// 公式のコード表の一部のみを含む([モジュールの説明](crate::registry)を参照)
+    pub fn bundled() -> &'static Registry {
+        static BUNDLED: OnceLock<Registry> = OnceLock::new();
+        BUNDLED.get_or_init(|| {
+            Registry::from_csv(BUNDLED_CSV).expect("bundled area code table is invalid")
+        })
+    }
+
+    /// `kind,code,name,parent_kind,parent_code,latitude,longitude`形式のCSVから読み込む
+    ///
+    /// `#`で始まる行はコメントで,`# version: `の行があればその値をバージョンとする。
+    pub fn from_csv(csv: &str) -> Result<Registry, RegistryError> {
+        let mut version = String::new();
+        let mut entries = Vec::new();
+        for (i, line) in csv.lines().enumerate() {
+            let line = line.trim();
+            if let Some(comment) = line.strip_prefix('#') {
+                if let Some(v) = comment.trim().strip_prefix("version:") {
+                    version = v.trim().to_string();
+                }
+                continue;
+            }
+            if line.is_empty() {
+                continue;
+            }
+            entries.push(
+                parse_line(line).map_err(|reason| RegistryError::InvalidLine {
+                    line: i + 1,
+                    reason,
+                })?,
+            );
+        }
+
+        let mut index = HashMap::new();
+        let mut by_code: HashMap<usize, Vec<usize>> = HashMap::new();
+        let mut children: HashMap<(AreaKind, usize), Vec<usize>> = HashMap::new();
+        for (i, entry) in entries.iter().enumerate() {
+            if index.insert((entry.kind, entry.code), i).is_some() {
+                return Err(RegistryError::Duplicated {
+                    kind: entry.kind.key(),
+                    code: entry.code,
+                });
+            }
+            by_code.entry(entry.code).or_default().push(i);
+            if let Some(parent) = entry.parent {
+                children.entry(parent).or_default().push(i);
+            }
+        }
+        for entry in &entries {
+            if let Some(parent) = entry.parent {
+                if !index.contains_key(&parent) {
+                    return Err(RegistryError::UnknownParent {
+                        code: entry.code,
+                        parent: parent.1,
+                    });
+                }
+            }
+        }
+
+        Ok(Registry {
+            version,
+            entries,
+            index,
+            by_code,
+            children,
+        })
+    }
+
+    /// コード表のバージョン
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// 全ての行
+    pub fn entries(&self) -> &[AreaEntry] {
+        &self.entries
+    }
+
+    /// 種別とコードで検索する
+    pub fn get(&self, kind: AreaKind, code: usize) -> Option<&AreaEntry> {
+        self.index.get(&(kind, code)).map(|&i| &self.entries[i])
+    }
+
+    /// コードで検索する
+    ///
+    /// 同じコードが複数の種別にある場合は全て返す
+    pub fn find(&self, code: usize) -> impl Iterator<Item = &AreaEntry> {
+        self.by_code
+            .get(&code)
+            .into_iter()
+            .flatten()
+            .map(move |&i| &self.entries[i])
+    }
+
+    /// 親の区域
+    pub fn parent(&self, entry: &AreaEntry) -> Option<&AreaEntry> {
+        let (kind, code) = entry.parent?;
+        self.get(kind, code)
+    }
+
+    /// 直下の区域,地点
+    pub fn children(&self, entry: &AreaEntry) -> impl Iterator<Item = &AreaEntry> {
+        self.children
+            .get(&(entry.kind, entry.code))
+            .into_iter()
+            .flatten()
+            .map(move |&i| &self.entries[i])
+    }
+
+    /// 指定したコードの区域に含まれる市町村等
+    ///
+    /// コードが複数の種別にある場合はそれら全ての区域について探す
+    pub fn municipalities_in(&self, code: usize) -> Vec<&AreaEntry> {
+        let mut result = Vec::new();
+        let mut stack: Vec<&AreaEntry> = self.find(code).collect();
+        while let Some(entry) = stack.pop() {
+            for child in self.children(entry) {
+                if child.kind == AreaKind::Municipality {
+                    result.push(child);
+                }
+                stack.push(child);
+            }
+        }
+        result.sort_by_key(|e| e.code);
+        result.dedup_by_key(|e| e.code);
+        result
+    }
+}
+
+fn parse_line(line: &str) -> Result<AreaEntry, String> {
+    let columns: Vec<&str> = line.split(',').map(str::trim).collect();
+    if columns.len() != 7 {
+        return Err(format!("expected 7 columns, found {}", columns.len()));
+    }
+    let kind =
+        AreaKind::from_key(columns[0]).ok_or_else(|| format!("unknown kind {}", columns[0]))?;
+    let code = parse_code(columns[1])?;
+    let parent = match (columns[3], columns[4]) {
+        ("", "") => None,
+        (kind, code) => Some((
+            AreaKind::from_key(kind).ok_or_else(|| format!("unknown kind {}", kind))?,
+            parse_code(code)?,
+        )),
+    };
+    let location = match (columns[5], columns[6]) {
+        ("", "") => None,
+        (latitude, longitude) => Some(Point {
+            latitude: parse_degree(latitude)?,
+            longitude: parse_degree(longitude)?,
+        }),
+    };
+    Ok(AreaEntry {
+        kind,
+        code,
+        name: columns[2].to_string(),
+        parent,
+        location,
+    })
+}
+
+fn parse_code(s: &str) -> Result<usize, String> {
+    s.parse().map_err(|_| format!("invalid code {}", s))
+}
+
+fn parse_degree(s: &str) -> Result<f64, String> {
+    s.parse().map_err(|_| format!("invalid degree {}", s))
+}
+
+/// 同梱のコード表から種別とコードで検索する
+pub fn get(kind: AreaKind, code: usize) -> Option<&'static AreaEntry> {
+    Registry::bundled().get(kind, code)
+}
+
+/// 同梱のコード表から,指定したコードの区域に含まれる市町村等を返す
+pub fn municipalities_in(code: usize) -> Vec<&'static AreaEntry> {
+    Registry::bundled().municipalities_in(code)
+}
+
+impl Area {
+    /// 同梱のコード表での行
+    ///
+    /// 同じコードが複数の種別にある場合(例えば270000の府県予報区と一次細分区域の大阪府)は,
+    /// 名前が一致するもののうち一次細分区域,市町村等をまとめた地域,市町村等,府県予報区の順に優先する。
+    /// 種別が分かっている場合は[`Area::entry_of`]を使う。
+    pub fn entry(&self) -> Option<&'static AreaEntry> {
+        const PRIORITY: [AreaKind; 4] = [
+            AreaKind::PrimarySubdivision,
+            AreaKind::MunicipalityGroup,
+            AreaKind::Municipality,
+            AreaKind::Prefecture,
+        ];
+        let registry = Registry::bundled();
+        let rank = |e: &&AreaEntry| {
+            (
+                e.name != self.name,
+                PRIORITY
+                    .iter()
+                    .position(|&k| k == e.kind)
+                    .unwrap_or(PRIORITY.len()),
+            )
+        };
+        registry.find(self.code).min_by_key(rank)
+    }
+
+    /// 同梱のコード表での指定した種別の行
+    pub fn entry_of(&self, kind: AreaKind) -> Option<&'static AreaEntry> {
+        get(kind, self.code)
+    }
+
+    /// 地域コードの種別
+    pub fn kind(&self) -> Option<AreaKind> {
+        self.entry().map(|e| e.kind)
+    }
+
+    /// 親の区域
+    pub fn parent(&self) -> Option<&'static AreaEntry> {
+        Registry::bundled().parent(self.entry()?)
+    }
+}
+
+impl Station {
+    /// 同梱のコード表での行
+    pub fn entry(&self) -> Option<&'static AreaEntry> {
+        get(AreaKind::Station, self.code)
+    }
+
+    /// 地点が属する一次細分区域のコード
+    pub fn area_code(&self) -> Option<usize> {
+        match self.entry()?.parent {
+            Some((AreaKind::PrimarySubdivision, code)) => Some(code),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(code: usize, name: &str) -> Area {
+        Area {
+            name: name.to_owned(),
+            code,
+        }
+    }
+
+    #[test]
+    fn get_by_kind_and_code() {
+        let tokyo = get(AreaKind::Prefecture, 130000).unwrap();
+        assert_eq!(tokyo.name, "東京都");
+        assert_eq!(tokyo.parent, None);
+
+        let station = get(AreaKind::Station, 44132).unwrap();
+        assert_eq!(station.name, "東京");
+        assert_eq!(station.parent, Some((AreaKind::PrimarySubdivision, 130010)));
+        assert!(station.location.is_some());
+
+        assert_eq!(
+            get(AreaKind::EarthquakeRegion, 331).unwrap().name,
+            "埼玉県南部"
+        );
+        assert_eq!(get(AreaKind::Station, 130000), None);
+        assert_eq!(get(AreaKind::Prefecture, 999999), None);
+    }
+
+    #[test]
+    fn same_code_in_different_kinds() {
+        let registry = Registry::bundled();
+        for (code, name, office) in [
+            (270000, "大阪府", 270000),
+            (370000, "香川県", 370000),
+            (11000, "宗谷地方", 11000),
+            (14030, "十勝地方", 14030),
+        ] {
+            let mut kinds = registry.find(code).map(|e| e.kind).collect::<Vec<_>>();
+            kinds.sort_by_key(|k| k.key());
+            assert_eq!(kinds, [AreaKind::PrimarySubdivision, AreaKind::Prefecture]);
+
+            let area = area(code, name);
+            assert_eq!(area.kind(), Some(AreaKind::PrimarySubdivision), "{}", name);
+            let parent = area.parent().unwrap();
+            assert_eq!((parent.kind, parent.code), (AreaKind::Prefecture, office));
+            assert_eq!(
+                area.entry_of(AreaKind::Prefecture).unwrap().parent,
+                None,
+                "{}",
+                name
+            );
+        }
+        let osaka = area(270000, "大阪府");
+        assert_eq!(
+            osaka.entry().unwrap(),
+            get(AreaKind::PrimarySubdivision, 270000).unwrap()
+        );
+        assert_eq!(osaka.entry_of(AreaKind::Station), None);
+        // 名前が一致しなくても一次細分区域を優先する
+        assert_eq!(
+            area(270000, "大阪").kind(),
+            Some(AreaKind::PrimarySubdivision)
+        );
+    }
+
+    #[test]
+    fn area_kind_and_parent() {
+        let tokyo = area(130010, "東京地方");
+        assert_eq!(tokyo.kind(), Some(AreaKind::PrimarySubdivision));
+        let parent = tokyo.parent().unwrap();
+        assert_eq!((parent.kind, parent.code), (AreaKind::Prefecture, 130000));
+
+        let ward = area(1310100, "千代田区");
+        assert_eq!(ward.kind(), Some(AreaKind::Municipality));
+        assert_eq!(ward.parent().unwrap().name, "２３区東部");
+        assert_eq!(area(130000, "東京都").parent(), None);
+        assert_eq!(area(999999, "不明").kind(), None);
+    }
+
+    #[test]
+    fn municipalities_in_tokyo() {
+        let municipalities = municipalities_in(130000);
+        assert_eq!(municipalities.len(), 62);
+        assert!(municipalities
+            .iter()
+            .all(|e| e.kind == AreaKind::Municipality));
+        assert!(municipalities.windows(2).all(|w| w[0].code < w[1].code));
+        let names = |code| {
+            municipalities_in(code)
+                .into_iter()
+                .map(|e| e.name.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(130020),
+            [
+                "大島町",
+                "利島村",
+                "新島村",
+                "神津島村",
+                "三宅村",
+                "御蔵島村"
+            ]
+        );
+        assert_eq!(names(130031), ["八丈町", "青ヶ島村"]);
+        assert_eq!(names(130011).len() + names(130012).len(), 23);
+        assert!(names(44132).is_empty());
+    }
+
+    #[test]
+    fn bundled_table_is_complete_for_offices() {
+        let registry = Registry::bundled();
+        let count = |kind| registry.entries().iter().filter(|e| e.kind == kind).count();
+        assert_eq!(count(AreaKind::PrimarySubdivision), 142);
+        for office in registry
+            .entries()
+            .iter()
+            .filter(|e| e.kind == AreaKind::Prefecture)
+        {
+            assert!(
+                registry
+                    .children(office)
+                    .any(|e| e.kind == AreaKind::PrimarySubdivision),
+                "{}",
+                office.name
+            );
+        }
+        for station in registry
+            .entries()
+            .iter()
+            .filter(|e| e.kind == AreaKind::Station)
+        {
+            let parent = registry.parent(station).unwrap();
+            assert_eq!(
+                parent.kind,
+                AreaKind::PrimarySubdivision,
+                "{}",
+                station.name
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_csv() {
+        let error = |csv| Registry::from_csv(csv).unwrap_err();
+        assert_eq!(
+            error("# version: test\noffice,130000,東京都,,,"),
+            RegistryError::InvalidLine {
+                line: 2,
+                reason: "expected 7 columns, found 6".to_owned()
+            }
+        );
+        assert_eq!(
+            error("office,130000,東京都,,,,\noffice,130000,東京都,,,,"),
+            RegistryError::Duplicated {
+                kind: "office",
+                code: 130000
+            }
+        );
+        assert_eq!(
+            error("class10,130010,東京地方,office,130000,,"),
+            RegistryError::UnknownParent {
+                code: 130010,
+                parent: 130000
+            }
+        );
+    }
+}
@@ -0,0 +1,373 @@
+//! 天気,風,波の予報文
+
+#[cfg(feature = "serialize")]
+use serde_derive::{Deserialize, Serialize};
+
+use crate::fuken_r1::{
+    Temporary, WaveHeightForecastPart, Weather, WeatherForecastPart, WindForecastPart,
+};
+use crate::wind::CompassDirection;
+
+/// 予報文の「後」「時々」「一時」
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum TimeModifier {
+    /// 後
+    Later,
+    /// 時々
+    Frequently,
+    /// 一時
+    Occasionally,
+}
+
+impl TimeModifier {
+    pub fn from_japanese(s: &str) -> Option<Self> {
+        match s.trim() {
+            "後" => Some(TimeModifier::Later),
+            "時々" => Some(TimeModifier::Frequently),
+            "一時" => Some(TimeModifier::Occasionally),
+            _ => None,
+        }
+    }
+
+    /// 「後時々」のように連なったもの,不明な部分以降は無視する
+    pub fn parse_sequence(s: &str) -> Vec<Self> {
+        let mut rest = s.trim_start_matches(char::is_whitespace);
+        let mut modifiers = Vec::new();
+        while let Some(modifier) = [
+            TimeModifier::Later,
+            TimeModifier::Frequently,
+            TimeModifier::Occasionally,
+        ]
+        .iter()
+        .find(|m| rest.starts_with(m.to_japanese()))
+        {
+            modifiers.push(*modifier);
+            rest = rest[modifier.to_japanese().len()..].trim_start_matches(char::is_whitespace);
+        }
+        modifiers
+    }
+
+    pub fn to_japanese(self) -> &'static str {
+        match self {
+            TimeModifier::Later => "後",
+            TimeModifier::Frequently => "時々",
+            TimeModifier::Occasionally => "一時",
+        }
+    }
+}
+
+/// 予報文の値に掛かる限定
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum Qualifier {
+    /// 所により
+    Locally,
+    /// 海上では,山沿いでは等
+    Place(String),
+    /// 夕方から,夜等
+    Time(String),
+}
+
+/// 予報文を区切った1つの値
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Phrase {
+    /// 直前の値からの変化,「後　時々」のように連なる場合は順に並ぶ
+    pub time_modifiers: Vec<TimeModifier>,
+    pub qualifiers: Vec<Qualifier>,
+    /// 全角数字を半角にした値
+    pub value: String,
+    /// やや強く,うねりを伴う等
+    pub remarks: Vec<String>,
+}
+
+impl Phrase {
+    /// 「１．５メートル」等の波の高さ(m)
+    pub fn meters(&self) -> Option<f64> {
+        self.value.strip_suffix("メートル")?.parse().ok()
+    }
+
+    /// 「北東の風」等の風向
+    pub fn wind_direction(&self) -> Option<CompassDirection> {
+        CompassDirection::from_japanese(self.value.strip_suffix("の風")?)
+    }
+}
+
+const TIMES: &[&str] = &[
+    "未明",
+    "明け方",
+    "朝",
+    "朝のうち",
+    "朝晩",
+    "朝夕",
+    "昼前",
+    "昼頃",
+    "昼過ぎ",
+    "日中",
+    "夕方",
+    "夜のはじめ頃",
+    "夜",
+    "夜遅く",
+    "夜半",
+];
+
+const REMARKS: &[&str] = &["やや強く", "強く", "非常に強く", "激しく"];
+
+/// 全角の数字,ピリオド,マイナス,スペースを半角にする
+pub fn normalize(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '０'..='９' => char::from(b'0' + (c as u32 - '０' as u32) as u8),
+            '．' => '.',
+            '－' => '-',
+            '　' => ' ',
+            c => c,
+        })
+        .collect()
+}
+
+/// 全角スペースで区切られた予報文を値毎に分ける
+pub fn parse(sentence: &str) -> Vec<Phrase> {
+    let normalized = normalize(sentence);
+    let tokens = normalized.split_whitespace().collect::<Vec<_>>();
+    let mut phrases = Vec::<Phrase>::new();
+    let mut time_modifiers = Vec::new();
+    let mut qualifiers = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = tokens[i];
+        let next = tokens.get(i + 1).copied();
+        i += 1;
+        let modifiers = TimeModifier::parse_sequence(token);
+        let modifiers_len = modifiers
+            .iter()
+            .map(|m| m.to_japanese().len())
+            .sum::<usize>();
+        if !modifiers.is_empty() && modifiers_len == token.len() {
+            time_modifiers.extend(modifiers);
+        } else if token == "で" {
+            // 「雨　で　雷を伴う」の接続
+        } else if token == "所により" {
+            qualifiers.push(Qualifier::Locally);
+        } else if next == Some("では") {
+            qualifiers.push(Qualifier::Place(format!("{}では", token)));
+            i += 1;
+        } else if next == Some("から") {
+            qualifiers.push(Qualifier::Time(format!("{}から", token)));
+            i += 1;
+        } else if TIMES.contains(&token) {
+            qualifiers.push(Qualifier::Time(token.to_owned()));
+        } else if let (Some(last), true) = (phrases.last_mut(), next == Some("を伴う")) {
+            last.remarks.push(format!("{}を伴う", token));
+            i += 1;
+        } else if let (Some(last), true) = (
+            phrases.last_mut(),
+            REMARKS.contains(&token) || token.ends_with("を伴う"),
+        ) {
+            last.remarks.push(token.to_owned());
+        } else {
+            phrases.push(Phrase {
+                time_modifiers: std::mem::take(&mut time_modifiers),
+                qualifiers: std::mem::take(&mut qualifiers),
+                value: token.to_owned(),
+                remarks: Vec::new(),
+            });
+        }
+    }
+    phrases
+}
+
+/// 天気の移り変わりの1区間
+#[derive(Debug, Clone)]
+pub struct WeatherSegment<'a> {
+    /// 直前の区間からの変化,最初の区間では空
+    pub time_modifiers: Vec<TimeModifier>,
+    pub weather: &'a Weather,
+}
+
+impl Temporary {
+    pub fn time_modifiers(&self) -> Vec<TimeModifier> {
+        TimeModifier::parse_sequence(&self.time_modifier)
+    }
+}
+
+impl WeatherForecastPart {
+    pub fn phrases(&self) -> Vec<Phrase> {
+        parse(&self.sentence)
+    }
+
+    /// BaseとTemporaryを順に並べたもの
+    ///
+    /// 「晴れ　後　くもり　時々　雨」は晴れ,後 くもり,時々 雨になる
+    pub fn timeline(&self) -> Vec<WeatherSegment<'_>> {
+        let base = WeatherSegment {
+            time_modifiers: Vec::new(),
+            weather: &self.base.weather,
+        };
+        std::iter::once(base)
+            .chain(self.temporary.iter().map(|t| WeatherSegment {
+                time_modifiers: t.time_modifiers(),
+                weather: &t.weather,
+            }))
+            .collect()
+    }
+}
+
+impl WindForecastPart {
+    pub fn phrases(&self) -> Vec<Phrase> {
+        parse(&self.sentence)
+    }
+}
+
+impl WaveHeightForecastPart {
+    pub fn phrases(&self) -> Vec<Phrase> {
+        parse(&self.sentence)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn phrase(time_modifiers: &[TimeModifier], qualifiers: &[Qualifier], value: &str) -> Phrase {
+        Phrase {
+            time_modifiers: time_modifiers.to_vec(),
+            qualifiers: qualifiers.to_vec(),
+            value: value.to_owned(),
+            remarks: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn parse_wind() {
+        let phrases = parse("北の風　後　北東の風　海上　では　北の風　やや強く");
+        let mut last = phrase(&[], &[Qualifier::Place("海上では".to_owned())], "北の風");
+        last.remarks.push("やや強く".to_owned());
+        assert_eq!(
+            phrases,
+            [
+                phrase(&[], &[], "北の風"),
+                phrase(&[TimeModifier::Later], &[], "北東の風"),
+                last,
+            ]
+        );
+        assert_eq!(
+            phrases[1].wind_direction(),
+            Some(CompassDirection::NorthEast)
+        );
+    }
+
+    #[test]
+    fn parse_wave_height() {
+        let phrases = parse("１メートル　後　１．５メートル");
+        assert_eq!(
+            phrases,
+            [
+                phrase(&[], &[], "1メートル"),
+                phrase(&[TimeModifier::Later], &[], "1.5メートル"),
+            ]
+        );
+        assert_eq!(
+            phrases.iter().map(Phrase::meters).collect::<Vec<_>>(),
+            [Some(1.0), Some(1.5)]
+        );
+    }
+
+    #[test]
+    fn parse_consecutive_time_modifiers() {
+        let expected = [
+            phrase(&[], &[], "くもり"),
+            phrase(&[TimeModifier::Later, TimeModifier::Frequently], &[], "雨"),
+        ];
+        assert_eq!(parse("くもり　後　時々　雨"), expected);
+        assert_eq!(parse("くもり　後時々　雨"), expected);
+    }
+
+    #[test]
+    fn parse_weather_with_qualifiers() {
+        let phrases = parse("くもり　時々　晴れ　所により　夕方　から　雨　で　雷を伴う");
+        let mut last = phrase(
+            &[],
+            &[Qualifier::Locally, Qualifier::Time("夕方から".to_owned())],
+            "雨",
+        );
+        last.remarks.push("雷を伴う".to_owned());
+        assert_eq!(
+            phrases,
+            [
+                phrase(&[], &[], "くもり"),
+                phrase(&[TimeModifier::Frequently], &[], "晴れ"),
+                last,
+            ]
+        );
+    }
+
+    /// R1の最初のWeatherForecastPartを置き換えて読む
+    fn weather_forecast_part(part: &str) -> WeatherForecastPart {
+        const R1: &str = include_str!("../benches/data/r1.xml");
+        let start = R1.find(r#"<WeatherForecastPart refID="1">"#).unwrap();
+        let end = start + R1[start..].find("</WeatherForecastPart>").unwrap();
+        let xml = format!(
+            "{}{}{}",
+            &R1[..start],
+            part,
+            &R1[end + "</WeatherForecastPart>".len()..]
+        );
+        let report: crate::fuken_r1::Report = xml.parse().unwrap();
+        let forecast = report.areas[0].weather_forecast.as_ref().unwrap();
+        forecast[0].weather_text.clone()
+    }
+
+    #[test]
+    fn timeline_with_temporary_parts() {
+        let part = weather_forecast_part(
+            r#"<WeatherForecastPart refID="1"><Sentence>晴れ　後　くもり　時々　雨</Sentence><Base><jmx_eb:Weather type="天気">晴れ</jmx_eb:Weather></Base><Temporary><TimeModifier>後</TimeModifier><jmx_eb:Weather type="天気">くもり</jmx_eb:Weather></Temporary><Temporary><TimeModifier>時々</TimeModifier><jmx_eb:Weather type="天気">雨</jmx_eb:Weather></Temporary></WeatherForecastPart>"#,
+        );
+        let timeline = part.timeline();
+        assert_eq!(
+            timeline
+                .iter()
+                .map(|s| (s.time_modifiers.clone(), s.weather.value.as_str()))
+                .collect::<Vec<_>>(),
+            [
+                (vec![], "晴れ"),
+                (vec![TimeModifier::Later], "くもり"),
+                (vec![TimeModifier::Frequently], "雨"),
+            ]
+        );
+        assert!(timeline.iter().all(|s| s.weather._type == "天気"));
+
+        // 予報文を区切ったものと同じ並び
+        let phrases = part.phrases();
+        assert_eq!(phrases.len(), timeline.len());
+        for (phrase, segment) in phrases.iter().zip(&timeline) {
+            assert_eq!(phrase.time_modifiers, segment.time_modifiers);
+            assert_eq!(phrase.value, segment.weather.value);
+        }
+    }
+
+    #[test]
+    fn timeline_with_consecutive_time_modifiers() {
+        let part = weather_forecast_part(
+            r#"<WeatherForecastPart refID="1"><Sentence>くもり　後　時々　雨</Sentence><Base><jmx_eb:Weather type="天気">くもり</jmx_eb:Weather></Base><Temporary><TimeModifier>後時々</TimeModifier><jmx_eb:Weather type="天気">雨</jmx_eb:Weather></Temporary></WeatherForecastPart>"#,
+        );
+        let timeline = part.timeline();
+        assert_eq!(timeline.len(), 2);
+        assert!(timeline[0].time_modifiers.is_empty());
+        assert_eq!(
+            timeline[1].time_modifiers,
+            [TimeModifier::Later, TimeModifier::Frequently]
+        );
+        assert_eq!(timeline[1].weather.value, "雨");
+
+        // Temporaryが無ければBaseのみ
+        let part = weather_forecast_part(
+            r#"<WeatherForecastPart refID="1"><Sentence>くもり</Sentence><Base><jmx_eb:Weather type="天気">くもり</jmx_eb:Weather></Base></WeatherForecastPart>"#,
+        );
+        let timeline = part.timeline();
+        assert_eq!(timeline.len(), 1);
+        assert!(timeline[0].time_modifiers.is_empty());
+        assert_eq!(timeline[0].weather.value, "くもり");
+    }
+}
@@ -0,0 +1,642 @@
+//! 震源・震度に関する情報
+
+use chrono::{DateTime, FixedOffset};
+use serde_derive::Deserialize;
+#[cfg(feature = "serialize")]
+use serde_derive::Serialize;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::de;
+use crate::error::ParseError;
+use crate::fuken_r1::{Addition, Control, Head};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Report {
+    pub control: Control,
+    pub head: Head,
+    /// 付加事項
+    pub addition: Option<Addition>,
+    /// 取消報等では存在しない
+    pub earthquake: Option<Earthquake>,
+    pub intensity: Option<Intensity>,
+    pub comments: Option<Comments>,
+}
+
+impl Report {
+    fn from_tmp(tmp: ReportTmp) -> Result<Self, ParseError> {
+        let ReportTmp {
+            control,
+            head,
+            addition,
+            body:
+                BodyTmp {
+                    earthquake,
+                    intensity,
+                    comments,
+                },
+        } = tmp;
+        Ok(Report {
+            control,
+            head,
+            addition,
+            earthquake: earthquake.map(Earthquake::from_tmp).transpose()?,
+            intensity,
+            comments,
+        })
+    }
+}
+
+impl FromStr for Report {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tmp: ReportTmp = de::from_str(s)?;
+        Report::from_tmp(tmp)
+    }
+}
+
+impl Report {
+    /// 読み込み元から直接パースする
+    ///
+    /// 省けるのは入力全体を`String`に読み込むことだけで,
+    /// 電文全体の要素の木は[`FromStr`]と同じくメモリ上に作られる
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, ParseError> {
+        let tmp: ReportTmp = de::from_reader(reader)?;
+        Report::from_tmp(tmp)
+    }
+
+    /// 読み込み済みの要素の木からパースする
+    pub(crate) fn from_document(document: &de::Document) -> Result<Self, ParseError> {
+        Report::from_tmp(document.deserialize()?)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Earthquake {
+    /// 地震の発生時刻
+    pub origin_time: Option<DateTime<FixedOffset>>,
+    /// 地震の発現時刻
+    pub arrival_time: DateTime<FixedOffset>,
+    pub hypocenter: Hypocenter,
+    pub magnitude: Magnitude,
+}
+
+impl Earthquake {
+    pub(crate) fn from_tmp(
+        EarthquakeTmp {
+            origin_time,
+            arrival_time,
+            hypocenter,
+            magnitude,
+        }: EarthquakeTmp,
+    ) -> Result<Self, ParseError> {
+        let HypocenterAreaTmp {
+            name,
+            code,
+            coordinate,
+            reduce_name,
+            reduce_code,
+            detailed_name,
+            detailed_code,
+        } = hypocenter.area;
+        let coordinate = coordinate
+            .iter()
+            .find_map(|c| c.value.as_deref().filter(|v| !v.trim().is_empty()))
+            .map(Coordinate::from_iso6709)
+            .transpose()?;
+        Ok(Earthquake {
+            origin_time,
+            arrival_time,
+            hypocenter: Hypocenter {
+                name,
+                code,
+                coordinate,
+                reduce_name,
+                reduce_code,
+                detailed_name,
+                detailed_code,
+                source: hypocenter.source,
+            },
+            magnitude: Magnitude::from_tmp(magnitude)?,
+        })
+    }
+}
+
+/// 震源要素
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Hypocenter {
+    /// 震央地名
+    pub name: String,
+    pub code: usize,
+    /// 震源要素不明の場合は`None`
+    pub coordinate: Option<Coordinate>,
+    /// 短縮用震央地名
+    pub reduce_name: Option<String>,
+    pub reduce_code: Option<usize>,
+    /// 詳細震央地名
+    pub detailed_name: Option<String>,
+    pub detailed_code: Option<usize>,
+    /// 震源決定機関
+    pub source: Option<String>,
+}
+
+/// 震源の位置
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Coordinate {
+    /// 緯度(度)
+    pub latitude: f64,
+    /// 経度(度)
+    pub longitude: f64,
+    /// 深さ(km),深さ不明の場合は`None`
+    pub depth: Option<f64>,
+}
+
+impl Coordinate {
+    /// `+35.7+139.8-10000/`のようなISO 6709形式の文字列を変換する
+    pub fn from_iso6709(s: &str) -> Result<Self, ParseError> {
+        let invalid = || ParseError::InvalidValue {
+            path: "Body/Earthquake/Hypocenter/Area/Coordinate".into(),
+            value: s.to_owned(),
+        };
+        let body = s.trim().trim_end_matches('/');
+        let mut components = Vec::new();
+        let mut start = None;
+        for (i, c) in body.char_indices() {
+            if c == '+' || c == '-' {
+                if let Some(start) = start {
+                    components.push(&body[start..i]);
+                }
+                start = Some(i);
+            } else if start.is_none() {
+                return Err(invalid());
+            }
+        }
+        if let Some(start) = start {
+            components.push(&body[start..]);
+        }
+        let (latitude, longitude, altitude) = match components.as_slice() {
+            [lat, lon] => (*lat, *lon, None),
+            [lat, lon, alt] => (*lat, *lon, Some(*alt)),
+            _ => return Err(invalid()),
+        };
+        let latitude = parse_angle(latitude, 2).ok_or_else(invalid)?;
+        let longitude = parse_angle(longitude, 3).ok_or_else(invalid)?;
+        let depth = match altitude {
+            Some(altitude) => Some(-altitude.parse::<f64>().map_err(|_| invalid())? / 1000.0),
+            None => None,
+        };
+        Ok(Coordinate {
+            latitude,
+            longitude,
+            depth,
+        })
+    }
+}
+
+/// 度,度分,度分秒のいずれかの形式の符号付き角度を度に変換する
+fn parse_angle(s: &str, degree_digits: usize) -> Option<f64> {
+    let (sign, digits) = if let Some(digits) = s.strip_prefix('+') {
+        (1.0, digits)
+    } else if let Some(digits) = s.strip_prefix('-') {
+        (-1.0, digits)
+    } else {
+        return None;
+    };
+    if !digits.is_ascii() {
+        return None;
+    }
+    let integer_len = digits.find('.').unwrap_or(digits.len());
+    let value = if integer_len == degree_digits {
+        digits.parse::<f64>().ok()?
+    } else if integer_len == degree_digits + 2 {
+        let degree = digits[..degree_digits].parse::<f64>().ok()?;
+        let minute = digits[degree_digits..].parse::<f64>().ok()?;
+        degree + minute / 60.0
+    } else if integer_len == degree_digits + 4 {
+        let degree = digits[..degree_digits].parse::<f64>().ok()?;
+        let minute = digits[degree_digits..degree_digits + 2]
+            .parse::<f64>()
+            .ok()?;
+        let second = digits[degree_digits + 2..].parse::<f64>().ok()?;
+        degree + minute / 60.0 + second / 3600.0
+    } else {
+        return None;
+    };
+    Some(sign * value)
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Magnitude {
+    /// Mj,Mw等
+    #[cfg_attr(feature = "serialize", serde(rename = "type"))]
+    pub _type: String,
+    pub value: MagnitudeValue,
+    pub description: Option<String>,
+}
+
+impl Magnitude {
+    fn from_tmp(
+        MagnitudeTmp {
+            _type,
+            condition,
+            description,
+            value,
+        }: MagnitudeTmp,
+    ) -> Result<Self, ParseError> {
+        // Ｍ８を超える巨大地震もcondition="不明"で送られるため,descriptionを先に見る
+        let over8 = description
+            .as_deref()
+            .is_some_and(|d| d.contains("８を超える") || d.contains("8を超える"));
+        let value = if over8 {
+            MagnitudeValue::Over8
+        } else if condition.as_deref().is_some_and(|c| c.contains("不明")) {
+            MagnitudeValue::Unknown
+        } else {
+            let value = value.unwrap_or_default();
+            MagnitudeValue::Value(value.trim().parse().map_err(|_| ParseError::InvalidValue {
+                path: "Body/Earthquake/jmx_eb:Magnitude".into(),
+                value,
+            })?)
+        };
+        Ok(Magnitude {
+            _type,
+            value,
+            description,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum MagnitudeValue {
+    Value(f64),
+    /// Ｍ不明
+    Unknown,
+    /// Ｍ８を超える巨大地震
+    Over8,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct Intensity {
+    #[serde(alias = "Observation")]
+    pub observation: Option<IntensityObservation>,
+}
+
+/// 震度の観測値
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct IntensityObservation {
+    #[serde(alias = "MaxInt")]
+    pub max_int: Option<SeismicIntensity>,
+    #[serde(alias = "Pref")]
+    #[serde(default)]
+    pub prefs: Vec<IntensityPref>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct IntensityPref {
+    #[serde(alias = "Name")]
+    pub name: String,
+    #[serde(alias = "Code")]
+    pub code: usize,
+    #[serde(alias = "MaxInt")]
+    pub max_int: Option<SeismicIntensity>,
+    #[serde(alias = "Revise")]
+    pub revise: Option<Revise>,
+    #[serde(alias = "Area")]
+    #[serde(default)]
+    pub areas: Vec<IntensityArea>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct IntensityArea {
+    #[serde(alias = "Name")]
+    pub name: String,
+    #[serde(alias = "Code")]
+    pub code: usize,
+    #[serde(alias = "MaxInt")]
+    pub max_int: Option<SeismicIntensity>,
+    #[serde(alias = "Revise")]
+    pub revise: Option<Revise>,
+    #[serde(alias = "City")]
+    #[serde(default)]
+    pub cities: Vec<IntensityCity>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct IntensityCity {
+    #[serde(alias = "Name")]
+    pub name: String,
+    #[serde(alias = "Code")]
+    pub code: usize,
+    #[serde(alias = "MaxInt")]
+    pub max_int: Option<SeismicIntensity>,
+    /// 震度５弱以上未入電等
+    #[serde(alias = "Condition")]
+    pub condition: Option<String>,
+    #[serde(alias = "Revise")]
+    pub revise: Option<Revise>,
+    #[serde(alias = "IntensityStation")]
+    #[serde(default)]
+    pub stations: Vec<IntensityStation>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct IntensityStation {
+    #[serde(alias = "Name")]
+    pub name: String,
+    #[serde(alias = "Code")]
+    pub code: usize,
+    #[serde(alias = "Int")]
+    pub int: Option<SeismicIntensity>,
+    #[serde(alias = "Revise")]
+    pub revise: Option<Revise>,
+}
+
+/// 震度階級
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub enum SeismicIntensity {
+    #[serde(alias = "1")]
+    One,
+    #[serde(alias = "2")]
+    Two,
+    #[serde(alias = "3")]
+    Three,
+    #[serde(alias = "4")]
+    Four,
+    #[serde(alias = "5-")]
+    FiveLower,
+    #[serde(alias = "5+")]
+    FiveUpper,
+    #[serde(alias = "6-")]
+    SixLower,
+    #[serde(alias = "6+")]
+    SixUpper,
+    #[serde(alias = "7")]
+    Seven,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub enum Revise {
+    #[serde(alias = "上方修正")]
+    Upward,
+    #[serde(alias = "追加")]
+    Added,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct Comments {
+    /// 固定付加文(津波に関する付加文等)
+    #[serde(alias = "ForecastComment")]
+    pub forecast_comment: Option<Comment>,
+    /// 固定付加文(その他)
+    #[serde(alias = "VarComment")]
+    pub var_comment: Option<Comment>,
+    /// 自由付加文
+    #[serde(alias = "FreeFormComment")]
+    pub free_form_comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct Comment {
+    #[serde(alias = "codeType")]
+    pub code_type: Option<String>,
+    #[serde(alias = "Text")]
+    pub text: String,
+    /// 空白区切りの付加文コード
+    #[serde(alias = "Code")]
+    pub code: String,
+}
+
+impl Comment {
+    pub fn codes(&self) -> impl Iterator<Item = &str> {
+        self.code.split_whitespace()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ReportTmp {
+    #[serde(alias = "Control")]
+    control: Control,
+    #[serde(alias = "Head")]
+    head: Head,
+    #[serde(alias = "Body")]
+    body: BodyTmp,
+    #[serde(alias = "jmx_add:Addition")]
+    addition: Option<Addition>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct BodyTmp {
+    #[serde(alias = "Earthquake")]
+    earthquake: Option<EarthquakeTmp>,
+    #[serde(alias = "Intensity")]
+    intensity: Option<Intensity>,
+    #[serde(alias = "Comments")]
+    comments: Option<Comments>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct EarthquakeTmp {
+    #[serde(alias = "OriginTime")]
+    origin_time: Option<DateTime<FixedOffset>>,
+    #[serde(alias = "ArrivalTime")]
+    arrival_time: DateTime<FixedOffset>,
+    #[serde(alias = "Hypocenter")]
+    hypocenter: HypocenterTmp,
+    #[serde(alias = "jmx_eb:Magnitude")]
+    magnitude: MagnitudeTmp,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct HypocenterTmp {
+    #[serde(alias = "Area")]
+    area: HypocenterAreaTmp,
+    #[serde(alias = "Source")]
+    source: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct HypocenterAreaTmp {
+    #[serde(alias = "Name")]
+    name: String,
+    #[serde(alias = "Code")]
+    code: usize,
+    #[serde(alias = "jmx_eb:Coordinate")]
+    #[serde(default)]
+    coordinate: Vec<CoordinateTmp>,
+    #[serde(alias = "ReduceName")]
+    reduce_name: Option<String>,
+    #[serde(alias = "ReduceCode")]
+    reduce_code: Option<usize>,
+    #[serde(alias = "DetailedName")]
+    detailed_name: Option<String>,
+    #[serde(alias = "DetailedCode")]
+    detailed_code: Option<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct CoordinateTmp {
+    #[serde(alias = "$value")]
+    #[serde(default)]
+    value: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct MagnitudeTmp {
+    #[serde(alias = "type")]
+    _type: String,
+    condition: Option<String>,
+    description: Option<String>,
+    #[serde(alias = "$value")]
+    #[serde(default)]
+    value: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VXSE53: &str = include_str!("../benches/data/vxse53.xml");
+    const MAGNITUDE: &str =
+        r#"<jmx_eb:Magnitude type="Mj" description="Ｍ５．９">5.9</jmx_eb:Magnitude>"#;
+
+    fn with_magnitude(magnitude: &str) -> Result<Report, ParseError> {
+        VXSE53.replace(MAGNITUDE, magnitude).parse()
+    }
+
+    fn magnitude(report: &Report) -> MagnitudeValue {
+        report.earthquake.as_ref().unwrap().magnitude.value
+    }
+
+    #[test]
+    fn parse_earthquake() {
+        let report: Report = VXSE53.parse().unwrap();
+        let earthquake = report.earthquake.as_ref().unwrap();
+        assert_eq!(earthquake.hypocenter.name, "千葉県北西部");
+        assert_eq!(earthquake.hypocenter.code, 341);
+        assert_eq!(
+            earthquake.hypocenter.coordinate,
+            Some(Coordinate {
+                latitude: 35.6,
+                longitude: 140.1,
+                depth: Some(80.0),
+            })
+        );
+        assert_eq!(earthquake.magnitude._type, "Mj");
+        assert_eq!(earthquake.magnitude.value, MagnitudeValue::Value(5.9));
+        assert_eq!(
+            earthquake.magnitude.description.as_deref(),
+            Some("Ｍ５．９")
+        );
+    }
+
+    #[test]
+    fn parse_unknown_magnitude() {
+        let report = with_magnitude(
+            r#"<jmx_eb:Magnitude type="Mj" condition="不明" description="Ｍ不明">NaN</jmx_eb:Magnitude>"#,
+        )
+        .unwrap();
+        assert_eq!(magnitude(&report), MagnitudeValue::Unknown);
+    }
+
+    #[test]
+    fn parse_magnitude_over_8() {
+        let report = with_magnitude(
+            r#"<jmx_eb:Magnitude type="Mj" condition="不明" description="Ｍ８を超える巨大地震">NaN</jmx_eb:Magnitude>"#,
+        )
+        .unwrap();
+        assert_eq!(magnitude(&report), MagnitudeValue::Over8);
+    }
+
+    #[test]
+    fn invalid_magnitude() {
+        let error = with_magnitude(
+            r#"<jmx_eb:Magnitude type="Mj" description="Ｍ５．９">５．９</jmx_eb:Magnitude>"#,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            ParseError::InvalidValue { ref path, ref value }
+                if path == "Body/Earthquake/jmx_eb:Magnitude" && value == "５．９"
+        ));
+    }
+
+    #[test]
+    fn parse_intensity() {
+        let report: Report = VXSE53.parse().unwrap();
+        let observation = report.intensity.unwrap().observation.unwrap();
+        assert_eq!(observation.max_int, Some(SeismicIntensity::FiveUpper));
+        assert_eq!(observation.prefs.len(), 1);
+        let pref = &observation.prefs[0];
+        assert_eq!((pref.name.as_str(), pref.code), ("埼玉県", 11));
+        assert_eq!(pref.max_int, Some(SeismicIntensity::FiveUpper));
+        assert_eq!(pref.areas.len(), 1);
+        let area = &pref.areas[0];
+        assert_eq!((area.name.as_str(), area.code), ("埼玉県南部", 331));
+
+        let cities = area
+            .cities
+            .iter()
+            .map(|c| (c.code, c.max_int, c.revise, c.condition.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            cities,
+            [
+                (1120300, Some(SeismicIntensity::FiveUpper), None, None),
+                (
+                    1146500,
+                    Some(SeismicIntensity::FiveUpper),
+                    Some(Revise::Added),
+                    None
+                ),
+                (1100000, None, None, Some("震度５弱以上未入電")),
+            ]
+        );
+        let station = &area.cities[1].stations[0];
+        assert_eq!(station.name, "宮代町笠原＊");
+        assert_eq!(station.int, Some(SeismicIntensity::FiveUpper));
+        assert_eq!(station.revise, Some(Revise::Added));
+        assert!(area.cities[2].stations.is_empty());
+        assert!(SeismicIntensity::FiveUpper > SeismicIntensity::FiveLower);
+
+        let comments = report.comments.unwrap();
+        assert_eq!(
+            comments.var_comment.unwrap().codes().collect::<Vec<_>>(),
+            ["0262", "0256"]
+        );
+    }
+
+    #[test]
+    fn coordinate_from_iso6709() {
+        let coordinate = |s| Coordinate::from_iso6709(s).unwrap();
+        assert_eq!(
+            coordinate("+3536.0+14006.0-10000/"),
+            Coordinate {
+                latitude: 35.6,
+                longitude: 140.1,
+                depth: Some(10.0),
+            }
+        );
+        assert_eq!(coordinate("-12.5+170.0/").depth, None);
+        assert_eq!(coordinate("-12.5+170.0/").latitude, -12.5);
+        for s in ["", "35.6+140.1/", "+35.6/", "+35.6+140.1-10km/"] {
+            assert!(Coordinate::from_iso6709(s).is_err(), "{}", s);
+        }
+    }
+}
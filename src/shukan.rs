@@ -0,0 +1,754 @@
+//! 府県週間天気予報
+
+use chrono::{DateTime, FixedOffset};
+use serde_derive::Deserialize;
+#[cfg(feature = "serialize")]
+use serde_derive::Serialize;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::de;
+use crate::error::ParseError;
+use crate::fuken_r1::{Addition, Area, Control, Head, TimeDefine, Weather, WeatherCode};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Report {
+    pub control: Control,
+    pub head: Head,
+    /// 付加事項
+    pub addition: Option<Addition>,
+    /// 一次細分区域等毎の天気,降水確率,信頼度
+    pub areas: Vec<AreaForecast>,
+    /// 地点毎の最低,最高気温
+    pub stations: Vec<StationForecast>,
+    /// 平年値
+    pub normals: Vec<Normal>,
+}
+
+impl Report {
+    fn from_tmp(tmp: ReportTmp) -> Result<Self, ParseError> {
+        let ReportTmp {
+            control,
+            head,
+            addition,
+            body: BodyTmp {
+                meteorological_infos,
+            },
+        } = tmp;
+        let mut areas = Vec::new();
+        let mut stations = Vec::new();
+        let mut normals = Vec::new();
+        for MeteorologicalInfosTmp {
+            _type,
+            time_series_info,
+            meteorological_info,
+        } in meteorological_infos
+        {
+            for TimeSeriesInfo {
+                time_defines:
+                    TimeDefines {
+                        items: mut time_defines,
+                    },
+                items,
+            } in time_series_info
+            {
+                time_defines.sort_by_key(|t| t.time_id);
+                match _type.as_str() {
+                    "区域予報" => {
+                        for item in items {
+                            areas.push(AreaForecast::from_tmp(item, &time_defines)?);
+                        }
+                    }
+                    "地点予報" => {
+                        for item in items {
+                            stations.push(StationForecast::from_tmp(item, &time_defines)?);
+                        }
+                    }
+                    _ => return Err(ParseError::UnknownMeteorologicalInfosType(_type)),
+                }
+            }
+            for info in meteorological_info {
+                for item in info.items {
+                    normals.push(Normal::from_tmp(item, info.date_time, &info.duration)?);
+                }
+            }
+        }
+        Ok(Report {
+            control,
+            head,
+            addition,
+            areas,
+            stations,
+            normals,
+        })
+    }
+}
+
+impl FromStr for Report {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tmp: ReportTmp = de::from_str(s)?;
+        Report::from_tmp(tmp)
+    }
+}
+
+impl Report {
+    /// 読み込み元から直接パースする
+    ///
+    /// 省けるのは入力全体を`String`に読み込むことだけで,
+    /// 電文全体の要素の木は[`FromStr`]と同じくメモリ上に作られる
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, ParseError> {
+        let tmp: ReportTmp = de::from_reader(reader)?;
+        Report::from_tmp(tmp)
+    }
+
+    /// 読み込み済みの要素の木からパースする
+    pub(crate) fn from_document(document: &de::Document) -> Result<Self, ParseError> {
+        Report::from_tmp(document.deserialize()?)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct AreaForecast {
+    pub area: Area,
+    /// 日毎の予報
+    pub forecast: Vec<DailyForecast>,
+}
+
+impl AreaForecast {
+    fn from_tmp(
+        Item { kind, area }: Item,
+        time_defines: &[TimeDefine],
+    ) -> Result<Self, ParseError> {
+        let mut weather = Vec::new();
+        let mut weather_code = Vec::new();
+        let mut probability_of_precipitation = Vec::new();
+        let mut reliability = Vec::new();
+        for property in kind.into_iter().flat_map(|kind| kind.property) {
+            match property._type.as_str() {
+                "天気" => {
+                    weather = property
+                        .weather_part
+                        .ok_or(ParseError::MissingElement {
+                            path: "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/WeatherPart".into(),
+                        })?
+                        .weather;
+                    weather_code = property
+                        .weather_code_part
+                        .ok_or(ParseError::MissingElement {
+                            path: "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/WeatherCodePart".into(),
+                        })?
+                        .weather_code;
+                }
+                "降水確率" => {
+                    probability_of_precipitation = property
+                        .probability_of_precipitation_part
+                        .ok_or(ParseError::MissingElement {
+                            path: "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/ProbabilityOfPrecipitationPart".into(),
+                        })?
+                        .values;
+                }
+                "信頼度" => {
+                    reliability = property
+                        .reliability_class_part
+                        .ok_or(ParseError::MissingElement {
+                            path: "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/ReliabilityClassPart".into(),
+                        })?
+                        .values;
+                }
+                _ => {}
+            }
+        }
+        let forecast = time_defines
+            .iter()
+            .map(|time| {
+                Ok(DailyForecast {
+                    time: time.clone(),
+                    weather: weather
+                        .iter()
+                        .find(|w| w.ref_id == Some(time.time_id))
+                        .cloned(),
+                    weather_code: weather_code
+                        .iter()
+                        .find(|w| w.ref_id == time.time_id)
+                        .cloned(),
+                    probability_of_precipitation: parse_value(
+                        find_value(&probability_of_precipitation, time.time_id),
+                        "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/ProbabilityOfPrecipitationPart",
+                    )?,
+                    reliability: Reliability::from_tmp(find_value(&reliability, time.time_id))?,
+                })
+            })
+            .collect::<Result<_, ParseError>>()?;
+        Ok(AreaForecast { area, forecast })
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct DailyForecast {
+    pub time: TimeDefine,
+    pub weather: Option<Weather>,
+    pub weather_code: Option<WeatherCode>,
+    /// 日降水確率(%)
+    pub probability_of_precipitation: Option<u32>,
+    pub reliability: Option<Reliability>,
+}
+
+/// 降水の有無の予報の信頼度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum Reliability {
+    A,
+    B,
+    C,
+}
+
+impl Reliability {
+    fn from_tmp(value: Option<&Value>) -> Result<Option<Self>, ParseError> {
+        match value.and_then(|v| v.value.as_deref()).map(str::trim) {
+            None | Some("") | Some("/") => Ok(None),
+            Some("A") | Some("Ａ") => Ok(Some(Reliability::A)),
+            Some("B") | Some("Ｂ") => Ok(Some(Reliability::B)),
+            Some("C") | Some("Ｃ") => Ok(Some(Reliability::C)),
+            Some(v) => Err(ParseError::InvalidValue {
+                path: "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/ReliabilityClassPart".into(),
+                value: v.to_owned(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct StationForecast {
+    pub station: Area,
+    /// 日毎の最低,最高気温
+    pub forecast: Vec<DailyTemperature>,
+}
+
+impl StationForecast {
+    fn from_tmp(
+        Item { kind, area }: Item,
+        time_defines: &[TimeDefine],
+    ) -> Result<Self, ParseError> {
+        let mut values = Vec::new();
+        for property in kind.into_iter().flat_map(|kind| kind.property) {
+            if !matches!(
+                property._type.as_str(),
+                "最低気温" | "最低気温予想範囲" | "最高気温" | "最高気温予想範囲"
+            ) {
+                continue;
+            }
+            values.extend(
+                property
+                    .temperature_part
+                    .ok_or(ParseError::MissingElement {
+                        path: TEMPERATURE_PART_PATH.into(),
+                    })?
+                    .values,
+            );
+        }
+        let mut min = Vec::new();
+        let mut max = Vec::new();
+        for value in values {
+            let (target, slot) = match value._type.as_str() {
+                MIN_TEMPERATURE => (&mut min, RangeSlot::Value),
+                MIN_TEMPERATURE_LOWER => (&mut min, RangeSlot::Lower),
+                MIN_TEMPERATURE_UPPER => (&mut min, RangeSlot::Upper),
+                MAX_TEMPERATURE => (&mut max, RangeSlot::Value),
+                MAX_TEMPERATURE_LOWER => (&mut max, RangeSlot::Lower),
+                MAX_TEMPERATURE_UPPER => (&mut max, RangeSlot::Upper),
+                _ => {
+                    return Err(ParseError::InvalidValue {
+                        path: format!("{}/jmx_eb:Temperature@type", TEMPERATURE_PART_PATH).into(),
+                        value: value._type,
+                    })
+                }
+            };
+            target.push((slot, value));
+        }
+        let forecast = time_defines
+            .iter()
+            .map(|time| {
+                Ok(DailyTemperature {
+                    time: time.clone(),
+                    min: TemperatureRange::from_tmp(&min, time.time_id)?,
+                    max: TemperatureRange::from_tmp(&max, time.time_id)?,
+                })
+            })
+            .collect::<Result<_, ParseError>>()?;
+        Ok(StationForecast {
+            station: area,
+            forecast,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct DailyTemperature {
+    pub time: TimeDefine,
+    pub min: Option<TemperatureRange>,
+    pub max: Option<TemperatureRange>,
+}
+
+/// 予想気温とその予想範囲(度)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct TemperatureRange {
+    pub value: i32,
+    /// 予想範囲の下端
+    pub lower: Option<i32>,
+    /// 予想範囲の上端
+    pub upper: Option<i32>,
+}
+
+impl TemperatureRange {
+    fn from_tmp(values: &[(RangeSlot, Value)], ref_id: usize) -> Result<Option<Self>, ParseError> {
+        let find = |slot| {
+            parse_value(
+                values
+                    .iter()
+                    .find(|(s, v)| *s == slot && v.ref_id == Some(ref_id))
+                    .map(|(_, v)| v),
+                TEMPERATURE_PART_PATH,
+            )
+        };
+        let value = match find(RangeSlot::Value)? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        Ok(Some(TemperatureRange {
+            value,
+            lower: find(RangeSlot::Lower)?,
+            upper: find(RangeSlot::Upper)?,
+        }))
+    }
+}
+
+/// 予想気温の値が予想値,予想範囲の下端,上端のどれか
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RangeSlot {
+    Value,
+    Lower,
+    Upper,
+}
+
+/// 平年値
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Normal {
+    pub area: Area,
+    #[cfg_attr(feature = "serialize", serde(rename = "datetime"))]
+    pub date_time: DateTime<FixedOffset>,
+    pub duration: Option<String>,
+    /// 最低気温の平年値(度)
+    pub min_temperature: Option<f64>,
+    /// 最高気温の平年値(度)
+    pub max_temperature: Option<f64>,
+    /// 降水量の平年並の範囲(mm)
+    pub precipitation: Option<PrecipitationRange>,
+}
+
+impl Normal {
+    fn from_tmp(
+        Item { kind, area }: Item,
+        date_time: DateTime<FixedOffset>,
+        duration: &Option<String>,
+    ) -> Result<Self, ParseError> {
+        let mut normal = Normal {
+            area,
+            date_time,
+            duration: duration.clone(),
+            min_temperature: None,
+            max_temperature: None,
+            precipitation: None,
+        };
+        for property in kind.into_iter().flat_map(|kind| kind.property) {
+            match property._type.as_str() {
+                "最低気温" | "最高気温" => {
+                    let values = property
+                        .temperature_part
+                        .ok_or(ParseError::MissingElement {
+                            path: "Body/MeteorologicalInfos/MeteorologicalInfo/Item/Kind/Property/TemperaturePart".into(),
+                        })?
+                        .values;
+                    let value = parse_value(
+                        values.first(),
+                        "Body/MeteorologicalInfos/MeteorologicalInfo/Item/Kind/Property/TemperaturePart",
+                    )?;
+                    if property._type == "最低気温" {
+                        normal.min_temperature = value;
+                    } else {
+                        normal.max_temperature = value;
+                    }
+                }
+                "降水量" => {
+                    let values = property
+                        .precipitation_part
+                        .ok_or(ParseError::MissingElement {
+                            path: PRECIPITATION_PART_PATH.into(),
+                        })?
+                        .values;
+                    let mut range = PrecipitationRange {
+                        lower: None,
+                        upper: None,
+                    };
+                    for value in &values {
+                        let target = match value._type.as_str() {
+                            PRECIPITATION_NORMAL_LOWER => &mut range.lower,
+                            PRECIPITATION_NORMAL_UPPER => &mut range.upper,
+                            _ => {
+                                return Err(ParseError::InvalidValue {
+                                    path: format!(
+                                        "{}/jmx_eb:Precipitation@type",
+                                        PRECIPITATION_PART_PATH
+                                    )
+                                    .into(),
+                                    value: value._type.clone(),
+                                })
+                            }
+                        };
+                        *target = parse_value(Some(value), PRECIPITATION_PART_PATH)?;
+                    }
+                    normal.precipitation = Some(range);
+                }
+                _ => {}
+            }
+        }
+        Ok(normal)
+    }
+}
+
+/// 降水量の範囲(mm)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct PrecipitationRange {
+    pub lower: Option<f64>,
+    pub upper: Option<f64>,
+}
+
+const TEMPERATURE_PART_PATH: &str =
+    "Body/MeteorologicalInfos/TimeSeriesInfo/Item/Kind/Property/TemperaturePart";
+const PRECIPITATION_PART_PATH: &str =
+    "Body/MeteorologicalInfos/MeteorologicalInfo/Item/Kind/Property/PrecipitationPart";
+
+// 地点予報の気温,平年値の降水量の値を区別するtype属性
+const MIN_TEMPERATURE: &str = "最低気温";
+const MIN_TEMPERATURE_LOWER: &str = "最低気温予想範囲（下端）";
+const MIN_TEMPERATURE_UPPER: &str = "最低気温予想範囲（上端）";
+const MAX_TEMPERATURE: &str = "最高気温";
+const MAX_TEMPERATURE_LOWER: &str = "最高気温予想範囲（下端）";
+const MAX_TEMPERATURE_UPPER: &str = "最高気温予想範囲（上端）";
+const PRECIPITATION_NORMAL_LOWER: &str = "降水量平年並の範囲（下端）";
+const PRECIPITATION_NORMAL_UPPER: &str = "降水量平年並の範囲（上端）";
+
+fn find_value(values: &[Value], ref_id: usize) -> Option<&Value> {
+    values.iter().find(|v| v.ref_id == Some(ref_id))
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ReportTmp {
+    #[serde(alias = "Control")]
+    control: Control,
+    #[serde(alias = "Head")]
+    head: Head,
+    #[serde(alias = "Body")]
+    body: BodyTmp,
+    #[serde(alias = "jmx_add:Addition")]
+    addition: Option<Addition>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct BodyTmp {
+    #[serde(alias = "MeteorologicalInfos")]
+    meteorological_infos: Vec<MeteorologicalInfosTmp>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct MeteorologicalInfosTmp {
+    #[serde(alias = "type")]
+    _type: String,
+    #[serde(alias = "TimeSeriesInfo")]
+    #[serde(default)]
+    time_series_info: Vec<TimeSeriesInfo>,
+    #[serde(alias = "MeteorologicalInfo")]
+    #[serde(default)]
+    meteorological_info: Vec<MeteorologicalInfoTmp>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TimeSeriesInfo {
+    #[serde(alias = "TimeDefines")]
+    time_defines: TimeDefines,
+    #[serde(alias = "Item")]
+    items: Vec<Item>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TimeDefines {
+    #[serde(alias = "TimeDefine")]
+    items: Vec<TimeDefine>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct MeteorologicalInfoTmp {
+    #[serde(alias = "DateTime")]
+    date_time: DateTime<FixedOffset>,
+    #[serde(alias = "Duration")]
+    duration: Option<String>,
+    #[serde(alias = "Item")]
+    #[serde(default)]
+    items: Vec<Item>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Item {
+    #[serde(alias = "Kind")]
+    #[serde(default)]
+    kind: Vec<Kind>,
+    #[serde(alias = "Area")]
+    #[serde(alias = "Station")]
+    area: Area,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Kind {
+    #[serde(alias = "Property")]
+    #[serde(default)]
+    property: Vec<Property>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Property {
+    #[serde(alias = "Type")]
+    _type: String,
+    #[serde(alias = "WeatherPart")]
+    weather_part: Option<WeatherPart>,
+    #[serde(alias = "WeatherCodePart")]
+    weather_code_part: Option<WeatherCodePart>,
+    #[serde(alias = "ProbabilityOfPrecipitationPart")]
+    probability_of_precipitation_part: Option<ProbabilityOfPrecipitationPart>,
+    #[serde(alias = "ReliabilityClassPart")]
+    reliability_class_part: Option<ReliabilityClassPart>,
+    #[serde(alias = "TemperaturePart")]
+    temperature_part: Option<TemperaturePart>,
+    #[serde(alias = "PrecipitationPart")]
+    precipitation_part: Option<PrecipitationPart>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct WeatherPart {
+    #[serde(alias = "jmx_eb:Weather")]
+    #[serde(default)]
+    weather: Vec<Weather>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct WeatherCodePart {
+    #[serde(alias = "jmx_eb:WeatherCode")]
+    #[serde(default)]
+    weather_code: Vec<WeatherCode>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ProbabilityOfPrecipitationPart {
+    #[serde(alias = "jmx_eb:ProbabilityOfPrecipitation")]
+    #[serde(default)]
+    values: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ReliabilityClassPart {
+    #[serde(alias = "jmx_eb:ReliabilityClass")]
+    #[serde(default)]
+    values: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TemperaturePart {
+    #[serde(alias = "jmx_eb:Temperature")]
+    #[serde(default)]
+    values: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct PrecipitationPart {
+    #[serde(alias = "jmx_eb:Precipitation")]
+    #[serde(default)]
+    values: Vec<Value>,
+}
+
+/// 値が省略されうる要素
+#[derive(Debug, Clone, Deserialize)]
+struct Value {
+    #[serde(alias = "refID")]
+    ref_id: Option<usize>,
+    #[serde(alias = "type")]
+    _type: String,
+    #[serde(alias = "$value")]
+    #[serde(default)]
+    value: Option<String>,
+}
+
+fn parse_value<T: FromStr>(
+    value: Option<&Value>,
+    path: &'static str,
+) -> Result<Option<T>, ParseError> {
+    match value.and_then(|v| v.value.as_deref()).map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(v) => v.parse().map(Some).map_err(|_| ParseError::InvalidValue {
+            path: path.into(),
+            value: v.to_owned(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VPFW50: &str = include_str!("../benches/data/vpfw50.xml");
+
+    fn range(value: i32, lower: i32, upper: i32) -> Option<TemperatureRange> {
+        Some(TemperatureRange {
+            value,
+            lower: Some(lower),
+            upper: Some(upper),
+        })
+    }
+
+    #[test]
+    fn parse_area_forecast() {
+        let report: Report = VPFW50.parse().unwrap();
+        let codes = report.areas.iter().map(|a| a.area.code).collect::<Vec<_>>();
+        assert_eq!(codes, [130010, 130020]);
+        let tokyo = &report.areas[0];
+        assert_eq!(tokyo.forecast.len(), 7);
+        assert_eq!(tokyo.forecast[0].weather.as_ref().unwrap().value, "雨");
+        assert_eq!(tokyo.forecast[3].weather_code.as_ref().unwrap().value, 212);
+
+        let probability = tokyo
+            .forecast
+            .iter()
+            .map(|f| f.probability_of_precipitation)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            probability,
+            [
+                None,
+                Some(20),
+                Some(10),
+                Some(50),
+                Some(30),
+                Some(10),
+                Some(20)
+            ]
+        );
+        let reliability = tokyo
+            .forecast
+            .iter()
+            .map(|f| f.reliability)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            reliability,
+            [
+                None,
+                None,
+                Some(Reliability::A),
+                Some(Reliability::C),
+                Some(Reliability::B),
+                Some(Reliability::A),
+                Some(Reliability::B)
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_temperature_ranges() {
+        let report: Report = VPFW50.parse().unwrap();
+        assert_eq!(report.stations.len(), 2);
+        let tokyo = &report.stations[0];
+        assert_eq!(tokyo.station.code, 44132);
+        assert_eq!(tokyo.forecast[0].min, None);
+        assert_eq!(tokyo.forecast[0].max, None);
+        assert_eq!(tokyo.forecast[1].min, range(19, 18, 20));
+        assert_eq!(tokyo.forecast[1].max, range(27, 25, 29));
+        assert_eq!(tokyo.forecast[6].max, range(28, 25, 30));
+        let hachijo = &report.stations[1];
+        assert_eq!(hachijo.forecast[3].min, range(23, 22, 24));
+
+        // 予想範囲の無い日
+        let report: Report = VPFW50
+            .replace(
+                r#"<jmx_eb:Temperature type="最高気温予想範囲（下端）" unit="度" refID="2">25</jmx_eb:Temperature>"#,
+                "",
+            )
+            .parse()
+            .unwrap();
+        assert_eq!(
+            report.stations[0].forecast[1].max,
+            Some(TemperatureRange {
+                value: 27,
+                lower: None,
+                upper: Some(29),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_normals() {
+        let report: Report = VPFW50.parse().unwrap();
+        assert_eq!(report.normals.len(), 3);
+        assert_eq!(report.normals[0].area.code, 130010);
+        assert_eq!(
+            report.normals[0].precipitation,
+            Some(PrecipitationRange {
+                lower: Some(11.0),
+                upper: Some(36.0),
+            })
+        );
+        assert_eq!(report.normals[1].area.code, 44132);
+        assert_eq!(report.normals[1].min_temperature, Some(17.1));
+        assert_eq!(report.normals[1].max_temperature, Some(24.9));
+        assert_eq!(report.normals[1].precipitation, None);
+    }
+
+    #[test]
+    fn unknown_type_is_an_error() {
+        let error = VPFW50
+            .replacen("最低気温予想範囲（下端）", "最低気温予想範囲の下端", 1)
+            .parse::<Report>()
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            ParseError::InvalidValue { ref path, ref value }
+                if path.ends_with("TemperaturePart/jmx_eb:Temperature@type")
+                    && value == "最低気温予想範囲の下端"
+        ));
+
+        let error = VPFW50
+            .replace("降水量平年並の範囲（上端）", "降水量平年並の範囲上端")
+            .parse::<Report>()
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            ParseError::InvalidValue { ref path, .. }
+                if path.ends_with("PrecipitationPart/jmx_eb:Precipitation@type")
+        ));
+
+        let error = VPFW50
+            .replacen(
+                r#"refID="3">A</jmx_eb:ReliabilityClass>"#,
+                r#"refID="3">D</jmx_eb:ReliabilityClass>"#,
+                1,
+            )
+            .parse::<Report>()
+            .unwrap_err();
+        assert!(matches!(error, ParseError::InvalidValue { ref value, .. } if value == "D"));
+    }
+}
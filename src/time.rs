@@ -0,0 +1,90 @@
+//! 日時の変換
+
+use chrono::{DateTime, FixedOffset, Local, TimeZone, Utc};
+
+/// 日本標準時(UTC+9)
+pub fn jst() -> FixedOffset {
+    FixedOffset::east_opt(9 * 3600).expect("UTC+9 is a valid offset")
+}
+
+/// 日本標準時で表した日時,「今日」「明日」の日付はこれで決める
+pub fn to_jst<Tz: TimeZone>(datetime: &DateTime<Tz>) -> DateTime<FixedOffset> {
+    datetime.with_timezone(&jst())
+}
+
+pub fn to_utc<Tz: TimeZone>(datetime: &DateTime<Tz>) -> DateTime<Utc> {
+    datetime.with_timezone(&Utc)
+}
+
+/// 実行環境のタイムゾーンで表した日時
+pub fn to_local<Tz: TimeZone>(datetime: &DateTime<Tz>) -> DateTime<Local> {
+    datetime.with_timezone(&Local)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+
+    use super::*;
+    use crate::fuken_r1::{DailyTemperatureForecast, Report};
+
+    const R1: &str = include_str!("../benches/data/r1.xml");
+    const CHILD: &str = "JMA_XML_PARSER_TZ_CHILD";
+
+    fn daily_temperatures(report: &Report) -> Vec<Vec<DailyTemperatureForecast>> {
+        report
+            .stations
+            .iter()
+            .map(|s| s.daily_temperatures())
+            .collect()
+    }
+
+    /// TZを変えた子プロセスでこのテストを実行し,パース結果と環境のオフセットを比べる
+    #[test]
+    fn parse_independent_of_tz() {
+        let report: Report = R1.parse().unwrap();
+        if std::env::var_os(CHILD).is_some() {
+            let offset = to_local(&report.control.datetime).offset().to_string();
+            println!("OFFSET {}", offset);
+            println!("DATES {:?}", daily_temperatures(&report));
+            println!("REPORT {:?}", report);
+            return;
+        }
+
+        let run = |tz: &str| {
+            let output = Command::new(std::env::current_exe().unwrap())
+                .args([
+                    "--exact",
+                    "time::tests::parse_independent_of_tz",
+                    "--nocapture",
+                ])
+                .args(["--test-threads", "1"])
+                .env(CHILD, "1")
+                .env("TZ", tz)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "TZ={}", tz);
+            let stdout = String::from_utf8(output.stdout).unwrap();
+            let line = |key: &str| {
+                stdout
+                    .lines()
+                    .find_map(|l| l.split_once(key).map(|(_, value)| value))
+                    .unwrap_or_else(|| panic!("no {} for TZ={}", key, tz))
+                    .to_owned()
+            };
+            (line("OFFSET "), line("DATES "), line("REPORT "))
+        };
+        let utc = run("UTC0");
+        let jst = run("JST-9");
+        assert_eq!(utc.0, "+00:00");
+        assert_eq!(jst.0, "+09:00");
+        for tz in ["UTC", "Asia/Tokyo", "HST10"] {
+            let other = run(tz);
+            assert_eq!(other.1, utc.1, "TZ={}", tz);
+            assert_eq!(other.2, utc.2, "TZ={}", tz);
+        }
+        assert_eq!(jst.1, utc.1);
+        assert_eq!(jst.2, utc.2);
+        assert_eq!(utc.1, format!("{:?}", daily_temperatures(&report)));
+    }
+}
@@ -0,0 +1,661 @@
+//! 津波警報・注意報・予報,津波情報
+
+use chrono::{DateTime, FixedOffset};
+use serde_derive::Deserialize;
+#[cfg(feature = "serialize")]
+use serde_derive::Serialize;
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::de;
+use crate::error::ParseError;
+use crate::fuken_r1::{Addition, Area, Control, Head};
+use crate::shingen_shindo::{Comment, Earthquake, EarthquakeTmp};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Report {
+    pub control: Control,
+    pub head: Head,
+    /// 付加事項
+    pub addition: Option<Addition>,
+    pub tsunami: Option<Tsunami>,
+    /// 津波の原因となった地震
+    pub earthquakes: Vec<Earthquake>,
+    pub text: Option<String>,
+    pub comments: Option<Comments>,
+}
+
+impl Report {
+    fn from_tmp(tmp: ReportTmp) -> Result<Self, ParseError> {
+        let ReportTmp {
+            control,
+            head,
+            addition,
+            body:
+                BodyTmp {
+                    tsunami,
+                    earthquake,
+                    text,
+                    comments,
+                },
+        } = tmp;
+        let earthquakes = earthquake
+            .into_iter()
+            .map(Earthquake::from_tmp)
+            .collect::<Result<_, _>>()?;
+        Ok(Report {
+            control,
+            head,
+            addition,
+            tsunami: tsunami.map(Tsunami::from_tmp),
+            earthquakes,
+            text,
+            comments,
+        })
+    }
+}
+
+impl FromStr for Report {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tmp: ReportTmp = de::from_str(s)?;
+        Report::from_tmp(tmp)
+    }
+}
+
+impl Report {
+    /// 読み込み元から直接パースする
+    ///
+    /// 省けるのは入力全体を`String`に読み込むことだけで,
+    /// 電文全体の要素の木は[`FromStr`]と同じくメモリ上に作られる
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, ParseError> {
+        let tmp: ReportTmp = de::from_reader(reader)?;
+        Report::from_tmp(tmp)
+    }
+
+    /// 読み込み済みの要素の木からパースする
+    pub(crate) fn from_document(document: &de::Document) -> Result<Self, ParseError> {
+        Report::from_tmp(document.deserialize()?)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Tsunami {
+    /// 沿岸の観測値
+    pub observations: Vec<ObservationItem>,
+    /// 津波予報区毎の警報・注意報・予報
+    pub forecasts: Vec<ForecastItem>,
+    /// 沖合の観測値から推定した沿岸の津波
+    pub estimations: Vec<ForecastItem>,
+}
+
+impl Tsunami {
+    fn from_tmp(
+        TsunamiTmp {
+            observation,
+            forecast,
+            estimation,
+        }: TsunamiTmp,
+    ) -> Self {
+        Tsunami {
+            observations: observation
+                .map(|o| o.items)
+                .unwrap_or_default()
+                .into_iter()
+                .map(ObservationItem::from_tmp)
+                .collect(),
+            forecasts: forecast
+                .map(|f| f.items)
+                .unwrap_or_default()
+                .into_iter()
+                .map(ForecastItem::from_tmp)
+                .collect(),
+            estimations: estimation
+                .map(|e| e.items)
+                .unwrap_or_default()
+                .into_iter()
+                .map(ForecastItem::from_tmp)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct ForecastItem {
+    /// 津波予報区
+    pub area: Area,
+    pub category: Option<Category>,
+    pub first_height: Option<FirstHeight>,
+    pub max_height: Option<MaxHeight>,
+    /// 潮位観測点毎の満潮時刻,到達予想時刻
+    pub stations: Vec<ForecastStation>,
+}
+
+impl ForecastItem {
+    fn from_tmp(
+        ForecastItemTmp {
+            area,
+            category,
+            first_height,
+            max_height,
+            stations,
+        }: ForecastItemTmp,
+    ) -> Self {
+        ForecastItem {
+            area,
+            category,
+            first_height,
+            max_height: max_height.map(MaxHeight::from_tmp),
+            stations,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct Category {
+    #[serde(alias = "Kind")]
+    pub kind: TsunamiKind,
+    /// 直前に発表されていた種類
+    #[serde(alias = "LastKind")]
+    pub last_kind: Option<TsunamiKind>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct TsunamiKind {
+    #[serde(alias = "Name")]
+    pub name: String,
+    #[serde(alias = "Code")]
+    pub code: u32,
+}
+
+impl TsunamiKind {
+    pub fn category(&self) -> Option<TsunamiCategory> {
+        match self.code {
+            52 | 53 => Some(TsunamiCategory::MajorWarning),
+            51 => Some(TsunamiCategory::Warning),
+            62 => Some(TsunamiCategory::Advisory),
+            71..=73 => Some(TsunamiCategory::Forecast),
+            50 => Some(TsunamiCategory::WarningCancelled),
+            60 => Some(TsunamiCategory::AdvisoryCancelled),
+            0 => Some(TsunamiCategory::NoTsunami),
+            _ => None,
+        }
+    }
+}
+
+/// 津波警報等の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum TsunamiCategory {
+    /// 大津波警報
+    MajorWarning,
+    /// 津波警報
+    Warning,
+    /// 津波注意報
+    Advisory,
+    /// 津波予報(若干の海面変動)
+    Forecast,
+    /// 警報解除
+    WarningCancelled,
+    /// 津波注意報解除
+    AdvisoryCancelled,
+    /// 津波なし
+    NoTsunami,
+}
+
+/// 津波の到達予想時刻,第１波の観測値
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct FirstHeight {
+    #[serde(alias = "ArrivalTime")]
+    pub arrival_time: Option<DateTime<FixedOffset>>,
+    /// 押し,引き
+    #[serde(alias = "Initial")]
+    pub initial: Option<String>,
+    /// 津波到達中と推測,第１波の到達を確認等
+    #[serde(alias = "Condition")]
+    pub condition: Option<String>,
+    #[serde(alias = "Revise")]
+    pub revise: Option<String>,
+}
+
+/// 予想される津波の高さ,これまでの最大波の観測値
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct MaxHeight {
+    /// 最大波の観測時刻
+    #[cfg_attr(feature = "serialize", serde(rename = "datetime"))]
+    pub date_time: Option<DateTime<FixedOffset>>,
+    pub height: Option<TsunamiHeight>,
+    /// 観測中,重要等
+    pub condition: Option<String>,
+    pub revise: Option<String>,
+}
+
+impl MaxHeight {
+    fn from_tmp(
+        MaxHeightTmp {
+            date_time,
+            height,
+            condition,
+            revise,
+        }: MaxHeightTmp,
+    ) -> Self {
+        MaxHeight {
+            date_time,
+            height: height.map(TsunamiHeight::from_tmp),
+            condition,
+            revise,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct TsunamiHeight {
+    #[cfg_attr(feature = "serialize", serde(rename = "type"))]
+    pub _type: String,
+    pub unit: Option<String>,
+    pub value: TsunamiHeightValue,
+    /// 上昇中,以上等
+    pub condition: Option<String>,
+    pub description: Option<String>,
+}
+
+impl TsunamiHeight {
+    fn from_tmp(
+        TsunamiHeightTmp {
+            _type,
+            unit,
+            condition,
+            description,
+            value,
+        }: TsunamiHeightTmp,
+    ) -> Self {
+        let value = match description.as_deref() {
+            Some(d) if d.contains("巨大") => TsunamiHeightValue::Huge,
+            Some(d) if d.contains("高い") => TsunamiHeightValue::High,
+            _ => match value.as_deref().map(str::trim).map(f64::from_str) {
+                Some(Ok(value)) if value.is_finite() => TsunamiHeightValue::Value(value),
+                _ => TsunamiHeightValue::Unknown,
+            },
+        };
+        TsunamiHeight {
+            _type,
+            unit,
+            value,
+            condition,
+            description,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum TsunamiHeightValue {
+    /// 高さ(m)
+    Value(f64),
+    /// 大津波警報の定性的表現
+    Huge,
+    /// 津波警報の定性的表現
+    High,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct ForecastStation {
+    #[serde(alias = "Name")]
+    pub name: String,
+    #[serde(alias = "Code")]
+    pub code: usize,
+    #[serde(alias = "HighTideDateTime")]
+    #[serde(rename(serialize = "high_tide_datetime"), alias = "high_tide_datetime")]
+    pub high_tide_date_time: Option<DateTime<FixedOffset>>,
+    #[serde(alias = "FirstHeight")]
+    pub first_height: Option<FirstHeight>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct ObservationItem {
+    pub area: Option<Area>,
+    pub stations: Vec<ObservationStation>,
+}
+
+impl ObservationItem {
+    fn from_tmp(ObservationItemTmp { area, stations }: ObservationItemTmp) -> Self {
+        ObservationItem {
+            area,
+            stations: stations
+                .into_iter()
+                .map(
+                    |ObservationStationTmp {
+                         name,
+                         code,
+                         first_height,
+                         max_height,
+                     }| ObservationStation {
+                        name,
+                        code,
+                        first_height,
+                        max_height: max_height.map(MaxHeight::from_tmp),
+                    },
+                )
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct ObservationStation {
+    pub name: String,
+    pub code: usize,
+    pub first_height: Option<FirstHeight>,
+    pub max_height: Option<MaxHeight>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct Comments {
+    /// 固定付加文
+    #[serde(alias = "WarningComment")]
+    pub warning_comment: Option<Comment>,
+    /// 自由付加文
+    #[serde(alias = "FreeFormComment")]
+    pub free_form_comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ReportTmp {
+    #[serde(alias = "Control")]
+    control: Control,
+    #[serde(alias = "Head")]
+    head: Head,
+    #[serde(alias = "Body")]
+    body: BodyTmp,
+    #[serde(alias = "jmx_add:Addition")]
+    addition: Option<Addition>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct BodyTmp {
+    #[serde(alias = "Tsunami")]
+    tsunami: Option<TsunamiTmp>,
+    #[serde(alias = "Earthquake")]
+    #[serde(default)]
+    earthquake: Vec<EarthquakeTmp>,
+    #[serde(alias = "Text")]
+    text: Option<String>,
+    #[serde(alias = "Comments")]
+    comments: Option<Comments>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TsunamiTmp {
+    #[serde(alias = "Observation")]
+    observation: Option<ObservationTmp>,
+    #[serde(alias = "Forecast")]
+    forecast: Option<ForecastTmp>,
+    #[serde(alias = "Estimation")]
+    estimation: Option<ForecastTmp>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ObservationTmp {
+    #[serde(alias = "Item")]
+    #[serde(default)]
+    items: Vec<ObservationItemTmp>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ObservationItemTmp {
+    #[serde(alias = "Area")]
+    area: Option<Area>,
+    #[serde(alias = "Station")]
+    #[serde(default)]
+    stations: Vec<ObservationStationTmp>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ObservationStationTmp {
+    #[serde(alias = "Name")]
+    name: String,
+    #[serde(alias = "Code")]
+    code: usize,
+    #[serde(alias = "FirstHeight")]
+    first_height: Option<FirstHeight>,
+    #[serde(alias = "MaxHeight")]
+    max_height: Option<MaxHeightTmp>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ForecastTmp {
+    #[serde(alias = "Item")]
+    #[serde(default)]
+    items: Vec<ForecastItemTmp>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ForecastItemTmp {
+    #[serde(alias = "Area")]
+    area: Area,
+    #[serde(alias = "Category")]
+    category: Option<Category>,
+    #[serde(alias = "FirstHeight")]
+    first_height: Option<FirstHeight>,
+    #[serde(alias = "MaxHeight")]
+    max_height: Option<MaxHeightTmp>,
+    #[serde(alias = "Station")]
+    #[serde(default)]
+    stations: Vec<ForecastStation>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct MaxHeightTmp {
+    #[serde(alias = "DateTime")]
+    date_time: Option<DateTime<FixedOffset>>,
+    #[serde(alias = "jmx_eb:TsunamiHeight")]
+    height: Option<TsunamiHeightTmp>,
+    #[serde(alias = "Condition")]
+    condition: Option<String>,
+    #[serde(alias = "Revise")]
+    revise: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TsunamiHeightTmp {
+    #[serde(alias = "type")]
+    _type: String,
+    unit: Option<String>,
+    condition: Option<String>,
+    description: Option<String>,
+    #[serde(alias = "$value")]
+    #[serde(default)]
+    value: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VTSE41: &str = include_str!("../benches/data/vtse41.xml");
+
+    fn forecasts() -> Vec<ForecastItem> {
+        let report: Report = VTSE41.parse().unwrap();
+        report.tsunami.unwrap().forecasts
+    }
+
+    #[test]
+    fn category_codes() {
+        let category = |code| {
+            TsunamiKind {
+                name: String::new(),
+                code,
+            }
+            .category()
+        };
+        for (code, expected) in [
+            (53, TsunamiCategory::MajorWarning),
+            (52, TsunamiCategory::MajorWarning),
+            (51, TsunamiCategory::Warning),
+            (62, TsunamiCategory::Advisory),
+            (71, TsunamiCategory::Forecast),
+            (72, TsunamiCategory::Forecast),
+            (73, TsunamiCategory::Forecast),
+            (50, TsunamiCategory::WarningCancelled),
+            (60, TsunamiCategory::AdvisoryCancelled),
+            (0, TsunamiCategory::NoTsunami),
+        ] {
+            assert_eq!(category(code), Some(expected), "{}", code);
+        }
+        for code in [1, 54, 61, 70, 74, 99] {
+            assert_eq!(category(code), None, "{}", code);
+        }
+
+        let categories = forecasts()
+            .iter()
+            .map(|item| {
+                let category = item.category.as_ref().unwrap();
+                (
+                    item.area.code,
+                    category.kind.category().unwrap(),
+                    category.last_kind.as_ref().unwrap().category().unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            categories,
+            [
+                (
+                    210,
+                    TsunamiCategory::MajorWarning,
+                    TsunamiCategory::NoTsunami
+                ),
+                (201, TsunamiCategory::Advisory, TsunamiCategory::NoTsunami),
+                (220, TsunamiCategory::MajorWarning, TsunamiCategory::Warning),
+                (250, TsunamiCategory::Warning, TsunamiCategory::NoTsunami),
+                (
+                    300,
+                    TsunamiCategory::AdvisoryCancelled,
+                    TsunamiCategory::Advisory
+                ),
+                (
+                    301,
+                    TsunamiCategory::WarningCancelled,
+                    TsunamiCategory::Warning
+                ),
+                (310, TsunamiCategory::Forecast, TsunamiCategory::NoTsunami),
+            ]
+        );
+    }
+
+    #[test]
+    fn arrival_conditions() {
+        let forecasts = forecasts();
+        let first_heights = forecasts
+            .iter()
+            .map(|item| {
+                item.first_height
+                    .as_ref()
+                    .map(|f| (f.arrival_time.is_some(), f.condition.as_deref()))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            first_heights,
+            [
+                Some((false, Some("津波到達中と推測"))),
+                Some((true, None)),
+                Some((false, Some("第１波の到達を確認"))),
+                Some((false, Some("ただちに津波来襲と予測"))),
+                None,
+                None,
+                None,
+            ]
+        );
+        assert_eq!(
+            forecasts[1].first_height.as_ref().unwrap().arrival_time,
+            Some(DateTime::parse_from_rfc3339("2011-03-11T15:30:00+09:00").unwrap())
+        );
+
+        let station = &forecasts[3].stations[0];
+        assert_eq!((station.name.as_str(), station.code), ("相馬", 25001));
+        assert!(station.high_tide_date_time.is_some());
+        assert_eq!(
+            station.first_height.as_ref().unwrap().condition.as_deref(),
+            Some("ただちに津波来襲と予測")
+        );
+    }
+
+    #[test]
+    fn forecast_heights() {
+        let heights = forecasts()
+            .iter()
+            .map(|item| {
+                item.max_height
+                    .as_ref()
+                    .and_then(|m| m.height.as_ref())
+                    .map(|h| h.value)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            heights,
+            [
+                Some(TsunamiHeightValue::Huge),
+                Some(TsunamiHeightValue::Value(1.0)),
+                Some(TsunamiHeightValue::Value(10.0)),
+                Some(TsunamiHeightValue::High),
+                None,
+                None,
+                Some(TsunamiHeightValue::Unknown),
+            ]
+        );
+    }
+
+    #[test]
+    fn observed_max_heights() {
+        let report: Report = VTSE41.parse().unwrap();
+        let observations = report.tsunami.unwrap().observations;
+        assert_eq!(observations.len(), 1);
+        assert_eq!(observations[0].area.as_ref().unwrap().code, 210);
+        let stations = &observations[0].stations;
+        assert_eq!(stations.len(), 2);
+
+        let kamaishi = &stations[0];
+        assert_eq!(kamaishi.code, 21601);
+        let first = kamaishi.first_height.as_ref().unwrap();
+        assert_eq!(first.initial.as_deref(), Some("引き"));
+        let max = kamaishi.max_height.as_ref().unwrap();
+        assert_eq!(
+            max.date_time,
+            Some(DateTime::parse_from_rfc3339("2011-03-11T15:21:00+09:00").unwrap())
+        );
+        let height = max.height.as_ref().unwrap();
+        assert_eq!(height._type, "これまでの最大波の高さ");
+        assert_eq!(height.value, TsunamiHeightValue::Value(4.2));
+        assert_eq!(height.condition.as_deref(), Some("上昇中"));
+
+        let miyako = stations[1].max_height.as_ref().unwrap();
+        assert!(miyako.height.is_none());
+        assert_eq!(miyako.condition.as_deref(), Some("観測中"));
+        assert_eq!(
+            stations[1]
+                .first_height
+                .as_ref()
+                .unwrap()
+                .condition
+                .as_deref(),
+            Some("第１波識別不能")
+        );
+
+        assert_eq!(report.earthquakes.len(), 1);
+        assert_eq!(report.earthquakes[0].hypocenter.code, 288);
+    }
+}
@@ -0,0 +1,326 @@
+//! 天気予報用の天気コード
+
+#[cfg(feature = "serialize")]
+use serde_derive::Serialize;
+
+use crate::fuken_r1::WeatherCode;
+use WeatherCategory::*;
+use WeatherChange::*;
+
+/// 天気コード表の1行
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub struct WeatherCodeKind {
+    pub code: usize,
+    /// 昼の天気アイコン
+    pub day_icon: u16,
+    /// 夜の天気アイコン
+    pub night_icon: u16,
+    /// 天気予報文で最初に現れる天気,コードの百の位とは限らない(340「雪か雨」は雪)
+    pub category: WeatherCategory,
+    pub change: Option<WeatherChange>,
+    /// 天気予報文
+    pub japanese: &'static str,
+    pub english: &'static str,
+}
+
+/// 大まかな天気
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub enum WeatherCategory {
+    /// 晴
+    Clear,
+    /// 曇
+    Cloudy,
+    /// 雨
+    Rain,
+    /// 雪
+    Snow,
+}
+
+/// 天気の変化
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+pub enum WeatherChange {
+    /// 一時
+    Occasionally(WeatherCategory),
+    /// 時々
+    Frequently(WeatherCategory),
+    /// 後
+    Later(WeatherCategory),
+}
+
+impl WeatherCodeKind {
+    pub fn from_code(code: usize) -> Option<&'static WeatherCodeKind> {
+        TABLE
+            .binary_search_by_key(&code, |kind| kind.code)
+            .ok()
+            .map(|i| &TABLE[i])
+    }
+
+    /// コード順の全ての天気コード
+    pub fn all() -> &'static [WeatherCodeKind] {
+        TABLE
+    }
+}
+
+impl WeatherCode {
+    /// 天気コード表にないコードならNone
+    pub fn kind(&self) -> Option<&'static WeatherCodeKind> {
+        WeatherCodeKind::from_code(self.value)
+    }
+}
+
+const fn kind(
+    code: usize,
+    day_icon: u16,
+    night_icon: u16,
+    category: WeatherCategory,
+    change: Option<WeatherChange>,
+    japanese: &'static str,
+    english: &'static str,
+) -> WeatherCodeKind {
+    WeatherCodeKind {
+        code,
+        day_icon,
+        night_icon,
+        category,
+        change,
+        japanese,
+        english,
+    }
+}
+
+#[rustfmt::skip]
+static TABLE: &[WeatherCodeKind] = &[
+    kind(100, 100, 500, Clear, None, "晴", "CLEAR"),
+    kind(101, 101, 501, Clear, Some(Frequently(Cloudy)), "晴時々曇", "PARTLY CLOUDY"),
+    kind(102, 102, 502, Clear, Some(Occasionally(Rain)), "晴一時雨", "CLEAR, OCCASIONAL SCATTERED SHOWERS"),
+    kind(103, 102, 502, Clear, Some(Frequently(Rain)), "晴時々雨", "CLEAR, FREQUENT SCATTERED SHOWERS"),
+    kind(104, 104, 504, Clear, Some(Occasionally(Snow)), "晴一時雪", "CLEAR, SNOW FLURRIES"),
+    kind(105, 104, 504, Clear, Some(Frequently(Snow)), "晴時々雪", "CLEAR, FREQUENT SNOW FLURRIES"),
+    kind(106, 102, 502, Clear, Some(Occasionally(Rain)), "晴一時雨か雪", "CLEAR, OCCASIONAL SCATTERED SHOWERS OR SNOW FLURRIES"),
+    kind(107, 102, 502, Clear, Some(Frequently(Rain)), "晴時々雨か雪", "CLEAR, FREQUENT SCATTERED SHOWERS OR SNOW FLURRIES"),
+    kind(108, 102, 502, Clear, Some(Occasionally(Rain)), "晴一時雨か雷雨", "CLEAR, OCCASIONAL SCATTERED SHOWERS AND/OR THUNDER"),
+    kind(110, 110, 510, Clear, Some(Later(Cloudy)), "晴後時々曇", "CLEAR, PARTLY CLOUDY LATER"),
+    kind(111, 110, 510, Clear, Some(Later(Cloudy)), "晴後曇", "CLEAR, CLOUDY LATER"),
+    kind(112, 112, 512, Clear, Some(Later(Rain)), "晴後一時雨", "CLEAR, OCCASIONAL SCATTERED SHOWERS LATER"),
+    kind(113, 112, 512, Clear, Some(Later(Rain)), "晴後時々雨", "CLEAR, FREQUENT SCATTERED SHOWERS LATER"),
+    kind(114, 112, 512, Clear, Some(Later(Rain)), "晴後雨", "CLEAR, RAIN LATER"),
+    kind(115, 115, 515, Clear, Some(Later(Snow)), "晴後一時雪", "CLEAR, OCCASIONAL SNOW FLURRIES LATER"),
+    kind(116, 115, 515, Clear, Some(Later(Snow)), "晴後時々雪", "CLEAR, FREQUENT SNOW FLURRIES LATER"),
+    kind(117, 115, 515, Clear, Some(Later(Snow)), "晴後雪", "CLEAR, SNOW LATER"),
+    kind(118, 112, 512, Clear, Some(Later(Rain)), "晴後雨か雪", "CLEAR, RAIN OR SNOW LATER"),
+    kind(119, 112, 512, Clear, Some(Later(Rain)), "晴後雨か雷雨", "CLEAR, RAIN AND/OR THUNDER LATER"),
+    kind(120, 102, 502, Clear, Some(Occasionally(Rain)), "晴朝夕一時雨", "OCCASIONAL SCATTERED SHOWERS IN THE MORNING AND EVENING, CLEAR DURING THE DAY"),
+    kind(121, 102, 502, Clear, Some(Occasionally(Rain)), "晴朝の内一時雨", "OCCASIONAL SCATTERED SHOWERS IN THE MORNING, CLEAR DURING THE DAY"),
+    kind(122, 112, 512, Clear, Some(Later(Rain)), "晴夕方一時雨", "CLEAR, OCCASIONAL SCATTERED SHOWERS IN THE EVENING"),
+    kind(123, 100, 500, Clear, None, "晴山沿い雷雨", "CLEAR IN THE PLAINS, RAIN AND THUNDER NEAR MOUNTAINOUS AREAS"),
+    kind(124, 100, 500, Clear, None, "晴山沿い雪", "CLEAR IN THE PLAINS, SNOW NEAR MOUNTAINOUS AREAS"),
+    kind(125, 112, 512, Clear, Some(Later(Rain)), "晴午後は雷雨", "CLEAR, RAIN AND THUNDER IN THE AFTERNOON"),
+    kind(126, 112, 512, Clear, Some(Later(Rain)), "晴昼頃から雨", "CLEAR, RAIN IN THE AFTERNOON"),
+    kind(127, 112, 512, Clear, Some(Later(Rain)), "晴夕方から雨", "CLEAR, RAIN IN THE EVENING"),
+    kind(128, 112, 512, Clear, Some(Later(Rain)), "晴夜は雨", "CLEAR, RAIN IN THE NIGHT"),
+    kind(130, 100, 500, Clear, None, "朝の内霧後晴", "FOG IN THE MORNING, CLEAR LATER"),
+    kind(131, 100, 500, Clear, None, "晴明け方霧", "FOG AROUND DAWN, CLEAR LATER"),
+    kind(132, 101, 501, Clear, Some(Frequently(Cloudy)), "晴朝夕曇", "CLOUDY IN THE MORNING AND EVENING, CLEAR DURING THE DAY"),
+    kind(140, 102, 502, Clear, Some(Frequently(Rain)), "晴時々雨で雷を伴う", "CLEAR, FREQUENT SCATTERED SHOWERS AND THUNDER"),
+    kind(160, 104, 504, Clear, Some(Occasionally(Snow)), "晴一時雪か雨", "CLEAR, SNOW FLURRIES OR OCCASIONAL SCATTERED SHOWERS"),
+    kind(170, 104, 504, Clear, Some(Frequently(Snow)), "晴時々雪か雨", "CLEAR, FREQUENT SNOW FLURRIES OR SCATTERED SHOWERS"),
+    kind(181, 115, 515, Clear, Some(Later(Snow)), "晴後雪か雨", "CLEAR, SNOW OR RAIN LATER"),
+    kind(200, 200, 200, Cloudy, None, "曇", "CLOUDY"),
+    kind(201, 201, 601, Cloudy, Some(Frequently(Clear)), "曇時々晴", "MOSTLY CLOUDY"),
+    kind(202, 202, 202, Cloudy, Some(Occasionally(Rain)), "曇一時雨", "CLOUDY, OCCASIONAL SCATTERED SHOWERS"),
+    kind(203, 202, 202, Cloudy, Some(Frequently(Rain)), "曇時々雨", "CLOUDY, FREQUENT SCATTERED SHOWERS"),
+    kind(204, 204, 204, Cloudy, Some(Occasionally(Snow)), "曇一時雪", "CLOUDY, OCCASIONAL SNOW FLURRIES"),
+    kind(205, 204, 204, Cloudy, Some(Frequently(Snow)), "曇時々雪", "CLOUDY, FREQUENT SNOW FLURRIES"),
+    kind(206, 202, 202, Cloudy, Some(Occasionally(Rain)), "曇一時雨か雪", "CLOUDY, OCCASIONAL SCATTERED SHOWERS OR SNOW FLURRIES"),
+    kind(207, 202, 202, Cloudy, Some(Frequently(Rain)), "曇時々雨か雪", "CLOUDY, FREQUENT SCATTERED SHOWERS OR SNOW FLURRIES"),
+    kind(208, 202, 202, Cloudy, Some(Occasionally(Rain)), "曇一時雨か雷雨", "CLOUDY, OCCASIONAL SCATTERED SHOWERS AND/OR THUNDER"),
+    kind(209, 200, 200, Cloudy, None, "霧", "FOG"),
+    kind(210, 210, 610, Cloudy, Some(Later(Clear)), "曇後時々晴", "CLOUDY, PARTLY CLOUDY LATER"),
+    kind(211, 210, 610, Cloudy, Some(Later(Clear)), "曇後晴", "CLOUDY, CLEAR LATER"),
+    kind(212, 212, 212, Cloudy, Some(Later(Rain)), "曇後一時雨", "CLOUDY, OCCASIONAL SCATTERED SHOWERS LATER"),
+    kind(213, 212, 212, Cloudy, Some(Later(Rain)), "曇後時々雨", "CLOUDY, FREQUENT SCATTERED SHOWERS LATER"),
+    kind(214, 212, 212, Cloudy, Some(Later(Rain)), "曇後雨", "CLOUDY, RAIN LATER"),
+    kind(215, 215, 215, Cloudy, Some(Later(Snow)), "曇後一時雪", "CLOUDY, SNOW FLURRIES LATER"),
+    kind(216, 215, 215, Cloudy, Some(Later(Snow)), "曇後時々雪", "CLOUDY, FREQUENT SNOW FLURRIES LATER"),
+    kind(217, 215, 215, Cloudy, Some(Later(Snow)), "曇後雪", "CLOUDY, SNOW LATER"),
+    kind(218, 212, 212, Cloudy, Some(Later(Rain)), "曇後雨か雪", "CLOUDY, RAIN OR SNOW LATER"),
+    kind(219, 212, 212, Cloudy, Some(Later(Rain)), "曇後雨か雷雨", "CLOUDY, RAIN AND/OR THUNDER LATER"),
+    kind(220, 202, 202, Cloudy, Some(Occasionally(Rain)), "曇朝夕一時雨", "OCCASIONAL SCATTERED SHOWERS IN THE MORNING AND EVENING, CLOUDY DURING THE DAY"),
+    kind(221, 202, 202, Cloudy, Some(Occasionally(Rain)), "曇朝の内一時雨", "CLOUDY, OCCASIONAL SCATTERED SHOWERS IN THE MORNING"),
+    kind(222, 212, 212, Cloudy, Some(Later(Rain)), "曇夕方一時雨", "CLOUDY, OCCASIONAL SCATTERED SHOWERS IN THE EVENING"),
+    kind(223, 201, 601, Cloudy, Some(Frequently(Clear)), "曇日中時々晴", "CLOUDY IN THE MORNING AND EVENING, PARTLY CLOUDY DURING THE DAY"),
+    kind(224, 212, 212, Cloudy, Some(Later(Rain)), "曇昼頃から雨", "CLOUDY, RAIN IN THE AFTERNOON"),
+    kind(225, 212, 212, Cloudy, Some(Later(Rain)), "曇夕方から雨", "CLOUDY, RAIN IN THE EVENING"),
+    kind(226, 212, 212, Cloudy, Some(Later(Rain)), "曇夜は雨", "CLOUDY, RAIN IN THE NIGHT"),
+    kind(228, 215, 215, Cloudy, Some(Later(Snow)), "曇昼頃から雪", "CLOUDY, SNOW IN THE AFTERNOON"),
+    kind(229, 215, 215, Cloudy, Some(Later(Snow)), "曇夕方から雪", "CLOUDY, SNOW IN THE EVENING"),
+    kind(230, 215, 215, Cloudy, Some(Later(Snow)), "曇夜は雪", "CLOUDY, SNOW IN THE NIGHT"),
+    kind(231, 200, 200, Cloudy, None, "曇海上海岸は霧か霧雨", "CLOUDY, FOG OR DRIZZLING ON THE SEA AND NEAR SEASHORE"),
+    kind(240, 202, 202, Cloudy, Some(Frequently(Rain)), "曇時々雨で雷を伴う", "CLOUDY, FREQUENT SCATTERED SHOWERS AND THUNDER"),
+    kind(250, 204, 204, Cloudy, Some(Frequently(Snow)), "曇時々雪で雷を伴う", "CLOUDY, FREQUENT SNOW AND THUNDER"),
+    kind(260, 204, 204, Cloudy, Some(Occasionally(Snow)), "曇一時雪か雨", "CLOUDY, SNOW FLURRIES OR OCCASIONAL SCATTERED SHOWERS"),
+    kind(270, 204, 204, Cloudy, Some(Frequently(Snow)), "曇時々雪か雨", "CLOUDY, FREQUENT SNOW FLURRIES OR SCATTERED SHOWERS"),
+    kind(281, 215, 215, Cloudy, Some(Later(Snow)), "曇後雪か雨", "CLOUDY, SNOW OR RAIN LATER"),
+    kind(300, 300, 300, Rain, None, "雨", "RAIN"),
+    kind(301, 301, 701, Rain, Some(Frequently(Clear)), "雨時々晴", "RAIN, PARTLY CLOUDY"),
+    kind(302, 302, 302, Rain, None, "雨時々止む", "SHOWERS THROUGHOUT THE DAY"),
+    kind(303, 303, 303, Rain, Some(Frequently(Snow)), "雨時々雪", "RAIN, FREQUENT SNOW FLURRIES"),
+    kind(304, 300, 300, Rain, None, "雨か雪", "RAIN OR SNOW"),
+    kind(306, 300, 300, Rain, None, "大雨", "HEAVY RAIN"),
+    kind(308, 308, 308, Rain, None, "雨で暴風を伴う", "RAINSTORM"),
+    kind(309, 303, 303, Rain, Some(Occasionally(Snow)), "雨一時雪", "RAIN, OCCASIONAL SNOW"),
+    kind(311, 311, 711, Rain, Some(Later(Clear)), "雨後晴", "RAIN, CLEAR LATER"),
+    kind(313, 313, 313, Rain, Some(Later(Cloudy)), "雨後曇", "RAIN, CLOUDY LATER"),
+    kind(314, 314, 314, Rain, Some(Later(Snow)), "雨後時々雪", "RAIN, FREQUENT SNOW FLURRIES LATER"),
+    kind(315, 314, 314, Rain, Some(Later(Snow)), "雨後雪", "RAIN, SNOW LATER"),
+    kind(316, 311, 711, Rain, Some(Later(Clear)), "雨か雪後晴", "RAIN OR SNOW, CLEAR LATER"),
+    kind(317, 313, 313, Rain, Some(Later(Cloudy)), "雨か雪後曇", "RAIN OR SNOW, CLOUDY LATER"),
+    kind(320, 311, 711, Rain, Some(Later(Clear)), "朝の内雨後晴", "RAIN IN THE MORNING, CLEAR LATER"),
+    kind(321, 313, 313, Rain, Some(Later(Cloudy)), "朝の内雨後曇", "RAIN IN THE MORNING, CLOUDY LATER"),
+    kind(322, 303, 303, Rain, Some(Occasionally(Snow)), "雨朝晩一時雪", "OCCASIONAL SNOW IN THE MORNING AND EVENING, RAIN DURING THE DAY"),
+    kind(323, 311, 711, Rain, Some(Later(Clear)), "雨昼頃から晴", "RAIN, CLEAR IN THE AFTERNOON"),
+    kind(324, 311, 711, Rain, Some(Later(Clear)), "雨夕方から晴", "RAIN, CLEAR IN THE EVENING"),
+    kind(325, 311, 711, Rain, Some(Later(Clear)), "雨夜は晴", "RAIN, CLEAR IN THE NIGHT"),
+    kind(326, 314, 314, Rain, Some(Later(Snow)), "雨夕方から雪", "RAIN, SNOW IN THE EVENING"),
+    kind(327, 314, 314, Rain, Some(Later(Snow)), "雨夜は雪", "RAIN, SNOW IN THE NIGHT"),
+    kind(328, 300, 300, Rain, None, "雨一時強く降る", "RAIN, EXPECT OCCASIONAL HEAVY RAINFALL"),
+    kind(329, 300, 300, Rain, None, "雨一時みぞれ", "RAIN, OCCASIONAL SLEET"),
+    kind(340, 400, 400, Snow, None, "雪か雨", "SNOW OR RAIN"),
+    kind(350, 300, 300, Rain, None, "雨で雷を伴う", "RAIN AND THUNDER"),
+    kind(361, 411, 811, Snow, Some(Later(Clear)), "雪か雨後晴", "SNOW OR RAIN, CLEAR LATER"),
+    kind(371, 413, 413, Snow, Some(Later(Cloudy)), "雪か雨後曇", "SNOW OR RAIN, CLOUDY LATER"),
+    kind(400, 400, 400, Snow, None, "雪", "SNOW"),
+    kind(401, 401, 801, Snow, Some(Frequently(Clear)), "雪時々晴", "SNOW, FREQUENTLY CLEAR"),
+    kind(402, 402, 402, Snow, None, "雪時々止む", "SNOW THROUGHOUT THE DAY"),
+    kind(403, 403, 403, Snow, Some(Frequently(Rain)), "雪時々雨", "SNOW, FREQUENT SCATTERED SHOWERS"),
+    kind(405, 400, 400, Snow, None, "大雪", "HEAVY SNOW"),
+    kind(406, 406, 406, Snow, None, "風雪強い", "SNOWSTORM"),
+    kind(407, 406, 406, Snow, None, "暴風雪", "HEAVY SNOWSTORM"),
+    kind(409, 403, 403, Snow, Some(Occasionally(Rain)), "雪一時雨", "SNOW, OCCASIONAL SCATTERED SHOWERS"),
+    kind(411, 411, 811, Snow, Some(Later(Clear)), "雪後晴", "SNOW, CLEAR LATER"),
+    kind(413, 413, 413, Snow, Some(Later(Cloudy)), "雪後曇", "SNOW, CLOUDY LATER"),
+    kind(414, 414, 414, Snow, Some(Later(Rain)), "雪後雨", "SNOW, RAIN LATER"),
+    kind(420, 411, 811, Snow, Some(Later(Clear)), "朝の内雪後晴", "SNOW IN THE MORNING, CLEAR LATER"),
+    kind(421, 413, 413, Snow, Some(Later(Cloudy)), "朝の内雪後曇", "SNOW IN THE MORNING, CLOUDY LATER"),
+    kind(422, 414, 414, Snow, Some(Later(Rain)), "雪昼頃から雨", "SNOW, RAIN IN THE AFTERNOON"),
+    kind(423, 414, 414, Snow, Some(Later(Rain)), "雪夕方から雨", "SNOW, RAIN IN THE EVENING"),
+    kind(424, 414, 414, Snow, Some(Later(Rain)), "雪夜は雨", "SNOW, RAIN IN THE NIGHT"),
+    kind(425, 400, 400, Snow, None, "雪一時強く降る", "SNOW, EXPECT OCCASIONAL HEAVY SNOWFALL"),
+    kind(426, 400, 400, Snow, None, "雪後みぞれ", "SNOW, SLEET LATER"),
+    kind(427, 400, 400, Snow, None, "雪一時みぞれ", "SNOW, OCCASIONAL SLEET"),
+    kind(450, 400, 400, Snow, None, "雪で雷を伴う", "SNOW AND THUNDER"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 気象庁の天気予報で使われる天気コードの一覧
+    ///
+    /// 表とは別に気象庁の天気予報ページのコード表から写したもの
+    const OFFICIAL_CODES: &[usize] = &[
+        100, 101, 102, 103, 104, 105, 106, 107, 108, 110, 111, 112, 113, 114, 115, 116, 117, 118,
+        119, 120, 121, 122, 123, 124, 125, 126, 127, 128, 130, 131, 132, 140, 160, 170, 181, 200,
+        201, 202, 203, 204, 205, 206, 207, 208, 209, 210, 211, 212, 213, 214, 215, 216, 217, 218,
+        219, 220, 221, 222, 223, 224, 225, 226, 228, 229, 230, 231, 240, 250, 260, 270, 281, 300,
+        301, 302, 303, 304, 306, 308, 309, 311, 313, 314, 315, 316, 317, 320, 321, 322, 323, 324,
+        325, 326, 327, 328, 329, 340, 350, 361, 371, 400, 401, 402, 403, 405, 406, 407, 409, 411,
+        413, 414, 420, 421, 422, 423, 424, 425, 426, 427, 450,
+    ];
+
+    /// 気象庁のコード表から抜き出したコード,予報文,昼夜のアイコン,大まかな天気
+    #[rustfmt::skip]
+    const SPOT_CHECKS: &[(usize, &str, u16, u16, WeatherCategory)] = &[
+        (100, "晴", 100, 500, Clear),
+        (101, "晴時々曇", 101, 501, Clear),
+        (102, "晴一時雨", 102, 502, Clear),
+        (110, "晴後時々曇", 110, 510, Clear),
+        (112, "晴後一時雨", 112, 512, Clear),
+        (130, "朝の内霧後晴", 100, 500, Clear),
+        (181, "晴後雪か雨", 115, 515, Clear),
+        (200, "曇", 200, 200, Cloudy),
+        (201, "曇時々晴", 201, 601, Cloudy),
+        (209, "霧", 200, 200, Cloudy),
+        (211, "曇後晴", 210, 610, Cloudy),
+        (270, "曇時々雪か雨", 204, 204, Cloudy),
+        (300, "雨", 300, 300, Rain),
+        (301, "雨時々晴", 301, 701, Rain),
+        (308, "雨で暴風を伴う", 308, 308, Rain),
+        (311, "雨後晴", 311, 711, Rain),
+        (313, "雨後曇", 313, 313, Rain),
+        (340, "雪か雨", 400, 400, Snow),
+        (350, "雨で雷を伴う", 300, 300, Rain),
+        (361, "雪か雨後晴", 411, 811, Snow),
+        (400, "雪", 400, 400, Snow),
+        (401, "雪時々晴", 401, 801, Snow),
+        (406, "風雪強い", 406, 406, Snow),
+        (407, "暴風雪", 406, 406, Snow),
+        (450, "雪で雷を伴う", 400, 400, Snow),
+    ];
+
+    #[test]
+    fn table_covers_official_codes() {
+        let codes = WeatherCodeKind::all()
+            .iter()
+            .map(|kind| kind.code)
+            .collect::<Vec<_>>();
+        assert_eq!(codes, OFFICIAL_CODES);
+    }
+
+    #[test]
+    fn spot_check_against_official_table() {
+        for &(code, japanese, day_icon, night_icon, category) in SPOT_CHECKS {
+            let kind = WeatherCodeKind::from_code(code).unwrap();
+            assert_eq!(
+                (kind.japanese, kind.day_icon, kind.night_icon, kind.category),
+                (japanese, day_icon, night_icon, category),
+                "{}",
+                code
+            );
+        }
+    }
+
+    /// 大まかな天気は予報文で最初に現れる天気
+    #[test]
+    fn category_matches_first_weather_in_text() {
+        for kind in WeatherCodeKind::all() {
+            let first = kind.japanese.chars().find_map(|c| match c {
+                '晴' => Some(Clear),
+                '曇' => Some(Cloudy),
+                '雨' => Some(Rain),
+                '雪' => Some(Snow),
+                _ => None,
+            });
+            // 「霧」のみの209は曇
+            assert_eq!(first.unwrap_or(Cloudy), kind.category, "{}", kind.code);
+        }
+    }
+
+    /// 夜のアイコンは昼と同じか,晴を含むものは昼のアイコンに400を足したもの
+    #[test]
+    fn night_icons() {
+        for kind in WeatherCodeKind::all() {
+            let night = kind.night_icon;
+            assert!(
+                night == kind.day_icon || night == kind.day_icon + 400,
+                "{}",
+                kind.code
+            );
+        }
+    }
+
+    #[test]
+    fn table_is_sorted_for_binary_search() {
+        assert!(TABLE.windows(2).all(|w| w[0].code < w[1].code));
+        for code in 0..1000 {
+            let kind = WeatherCodeKind::from_code(code);
+            assert_eq!(kind.is_some(), OFFICIAL_CODES.contains(&code), "{}", code);
+            if let Some(kind) = kind {
+                assert_eq!(kind.code, code);
+            }
+        }
+    }
+}
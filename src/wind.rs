@@ -0,0 +1,236 @@
+//! 風向,風速階級
+
+#[cfg(feature = "serialize")]
+use serde_derive::{Deserialize, Serialize};
+
+use crate::fuken_r1::{WindDirection, WindSpeedLevel};
+
+/// 16方位と静穏,変化
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum CompassDirection {
+    /// 北
+    North,
+    /// 北北東
+    NorthNorthEast,
+    /// 北東
+    NorthEast,
+    /// 東北東
+    EastNorthEast,
+    /// 東
+    East,
+    /// 東南東
+    EastSouthEast,
+    /// 南東
+    SouthEast,
+    /// 南南東
+    SouthSouthEast,
+    /// 南
+    South,
+    /// 南南西
+    SouthSouthWest,
+    /// 南西
+    SouthWest,
+    /// 西南西
+    WestSouthWest,
+    /// 西
+    West,
+    /// 西北西
+    WestNorthWest,
+    /// 北西
+    NorthWest,
+    /// 北北西
+    NorthNorthWest,
+    /// 静穏
+    Calm,
+    /// 変化
+    Variable,
+}
+
+const POINTS: [(CompassDirection, &str); 16] = [
+    (CompassDirection::North, "北"),
+    (CompassDirection::NorthNorthEast, "北北東"),
+    (CompassDirection::NorthEast, "北東"),
+    (CompassDirection::EastNorthEast, "東北東"),
+    (CompassDirection::East, "東"),
+    (CompassDirection::EastSouthEast, "東南東"),
+    (CompassDirection::SouthEast, "南東"),
+    (CompassDirection::SouthSouthEast, "南南東"),
+    (CompassDirection::South, "南"),
+    (CompassDirection::SouthSouthWest, "南南西"),
+    (CompassDirection::SouthWest, "南西"),
+    (CompassDirection::WestSouthWest, "西南西"),
+    (CompassDirection::West, "西"),
+    (CompassDirection::WestNorthWest, "西北西"),
+    (CompassDirection::NorthWest, "北西"),
+    (CompassDirection::NorthNorthWest, "北北西"),
+];
+
+impl CompassDirection {
+    /// 風向の漢字表記から
+    pub fn from_japanese(s: &str) -> Option<Self> {
+        match s.trim() {
+            "静穏" => Some(CompassDirection::Calm),
+            "変化" => Some(CompassDirection::Variable),
+            s => POINTS
+                .iter()
+                .find(|(_, name)| *name == s)
+                .map(|(direction, _)| *direction),
+        }
+    }
+
+    pub fn to_japanese(self) -> &'static str {
+        match self {
+            CompassDirection::North => "北",
+            CompassDirection::NorthNorthEast => "北北東",
+            CompassDirection::NorthEast => "北東",
+            CompassDirection::EastNorthEast => "東北東",
+            CompassDirection::East => "東",
+            CompassDirection::EastSouthEast => "東南東",
+            CompassDirection::SouthEast => "南東",
+            CompassDirection::SouthSouthEast => "南南東",
+            CompassDirection::South => "南",
+            CompassDirection::SouthSouthWest => "南南西",
+            CompassDirection::SouthWest => "南西",
+            CompassDirection::WestSouthWest => "西南西",
+            CompassDirection::West => "西",
+            CompassDirection::WestNorthWest => "西北西",
+            CompassDirection::NorthWest => "北西",
+            CompassDirection::NorthNorthWest => "北北西",
+            CompassDirection::Calm => "静穏",
+            CompassDirection::Variable => "変化",
+        }
+    }
+
+    /// 北を0として時計回りの度数,静穏と変化はNone
+    pub fn degrees(self) -> Option<f64> {
+        POINTS
+            .iter()
+            .position(|(d, _)| *d == self)
+            .map(|i| i as f64 * 22.5)
+    }
+
+    /// 度数に最も近い16方位
+    pub fn from_degrees(degrees: f64) -> Option<Self> {
+        if !degrees.is_finite() {
+            return None;
+        }
+        let i = (degrees.rem_euclid(360.0) / 22.5).round() as usize % 16;
+        Some(POINTS[i].0)
+    }
+}
+
+impl WindDirection {
+    /// 未知の表記ならNone
+    pub fn direction(&self) -> Option<CompassDirection> {
+        CompassDirection::from_japanese(&self.value)
+    }
+}
+
+/// 風速の範囲(m/s),下端を含み上端を含まない
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct WindSpeedRange {
+    pub lower: f64,
+    /// 最も強い階級ではNone
+    pub upper: Option<f64>,
+}
+
+impl WindSpeedLevel {
+    /// range属性("0 3","20"等)の解釈,不正な値ならNone
+    pub fn speed_range(&self) -> Option<WindSpeedRange> {
+        let mut values = self.range.split_whitespace().map(str::parse::<f64>);
+        let lower = values.next()?.ok()?;
+        let upper = match values.next() {
+            Some(upper) => Some(upper.ok()?),
+            None => None,
+        };
+        if values.next().is_some() {
+            return None;
+        }
+        Some(WindSpeedRange { lower, upper })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(range: &str) -> WindSpeedLevel {
+        WindSpeedLevel {
+            description: String::new(),
+            range: range.to_owned(),
+            ref_id: 1,
+            _type: "風速階級".to_owned(),
+            value: 1,
+        }
+    }
+
+    #[test]
+    fn japanese_round_trip() {
+        let all = POINTS
+            .iter()
+            .map(|(d, _)| *d)
+            .chain([CompassDirection::Calm, CompassDirection::Variable]);
+        for direction in all {
+            let name = direction.to_japanese();
+            assert_eq!(CompassDirection::from_japanese(name), Some(direction));
+        }
+        assert_eq!(
+            CompassDirection::from_japanese(" 北北東 "),
+            Some(CompassDirection::NorthNorthEast)
+        );
+        assert_eq!(CompassDirection::from_japanese("北北北"), None);
+        assert_eq!(CompassDirection::from_japanese(""), None);
+    }
+
+    #[test]
+    fn degrees() {
+        assert_eq!(CompassDirection::North.degrees(), Some(0.0));
+        assert_eq!(CompassDirection::EastNorthEast.degrees(), Some(67.5));
+        assert_eq!(CompassDirection::NorthNorthWest.degrees(), Some(337.5));
+        assert_eq!(CompassDirection::Calm.degrees(), None);
+        assert_eq!(CompassDirection::Variable.degrees(), None);
+        for (direction, _) in POINTS {
+            let degrees = direction.degrees().unwrap();
+            assert_eq!(CompassDirection::from_degrees(degrees), Some(direction));
+        }
+    }
+
+    #[test]
+    fn from_degrees_wraps_around() {
+        let from = CompassDirection::from_degrees;
+        assert_eq!(from(359.0), Some(CompassDirection::North));
+        assert_eq!(from(360.0), Some(CompassDirection::North));
+        assert_eq!(from(348.75), Some(CompassDirection::North));
+        assert_eq!(from(348.0), Some(CompassDirection::NorthNorthWest));
+        assert_eq!(from(720.0 + 90.0), Some(CompassDirection::East));
+        assert_eq!(from(-1.0), Some(CompassDirection::North));
+        assert_eq!(from(-90.0), Some(CompassDirection::West));
+        assert_eq!(from(-22.5), Some(CompassDirection::NorthNorthWest));
+        assert_eq!(from(f64::NAN), None);
+        assert_eq!(from(f64::INFINITY), None);
+        assert_eq!(from(f64::NEG_INFINITY), None);
+    }
+
+    #[test]
+    fn speed_range() {
+        assert_eq!(
+            level("0 3").speed_range(),
+            Some(WindSpeedRange {
+                lower: 0.0,
+                upper: Some(3.0),
+            })
+        );
+        assert_eq!(
+            level(" 20 ").speed_range(),
+            Some(WindSpeedRange {
+                lower: 20.0,
+                upper: None,
+            })
+        );
+        for malformed in ["", "a", "0 b", "0 3 6", "３ ６"] {
+            assert_eq!(level(malformed).speed_range(), None, "{:?}", malformed);
+        }
+    }
+}